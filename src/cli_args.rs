@@ -0,0 +1,129 @@
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+
+use j_archive_crawler::{parser::TextFormat, serializer::OutputFormat};
+
+/// Program to crawl j-archive.com and parse jeopardy question data into json
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+pub struct CliArgs {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// The episode number to parse (note: if iteratons are applied, this will be the starting episode)
+    #[arg(short = 'e', long = "episode", default_value_t = 7515)]
+    // 7515 is 12/01/22 episode, --latest finds the newest one instead
+    pub episode_no: u32,
+
+    /// The number of iterations
+    #[arg(short = 'i', long = "iterations", default_value_t = 1)]
+    pub iterations: u16,
+
+    /// Crawl every game j-archive lists for this season (e.g. 39, or superjeopardy) instead of a range of episodes
+    #[arg(long = "season", conflicts_with_all = ["episode_no", "iterations"])]
+    pub season: Option<String>,
+
+    /// Crawl every game in every season j-archive lists, oldest first
+    #[arg(long = "all-seasons", conflicts_with_all = ["episode_no", "iterations", "season"])]
+    pub all_seasons: bool,
+
+    /// Only crawl games that aired on or after this date (YYYY-MM-DD), looked up through the season index
+    #[arg(long = "from", conflicts_with_all = ["episode_no", "iterations"])]
+    pub from: Option<NaiveDate>,
+
+    /// Only crawl games that aired on or before this date (YYYY-MM-DD), looked up through the season index
+    #[arg(long = "to", conflicts_with_all = ["episode_no", "iterations"])]
+    pub to: Option<NaiveDate>,
+
+    /// Crawl the newest game j-archive has
+    #[arg(long = "latest", conflicts_with_all = ["episode_no", "iterations", "season", "all_seasons", "from", "to"])]
+    pub latest: bool,
+
+    /// Crawl every game that aired since the newest one in this earlier output file or manifest, skipping games it already has
+    #[arg(long = "since-last-run", conflicts_with_all = ["episode_no", "iterations", "season", "all_seasons", "from", "to", "latest"])]
+    pub since_last_run: Option<String>,
+
+    /// Crawl every game this player (their j-archive player id) appeared in
+    #[arg(long = "player", conflicts_with_all = ["episode_no", "iterations", "season", "all_seasons", "from", "to", "latest", "since_last_run"])]
+    pub player: Option<u32>,
+
+    /// Write the player's profile (name, bio, winnings and appearances) to this file as json
+    #[arg(long = "player-outfile", requires = "player")]
+    pub player_outfile: Option<PathBuf>,
+
+    /// The maximum number of episodes to fetch at the same time
+    #[arg(short = 'c', long = "concurrency", default_value_t = 1)]
+    pub concurrency: u16,
+
+    /// The maximum number of requests per second sent to j-archive
+    #[arg(short = 'r', long = "requests-per-second", default_value_t = 2.0)]
+    pub requests_per_second: f64,
+
+    /// The number of requests that may be sent at once before rate limiting kicks in
+    #[arg(short = 'b', long = "burst", default_value_t = 4)]
+    pub burst: u32,
+
+    /// The number of times a timed out, throttled or failed request is retried
+    #[arg(long = "max-retries", default_value_t = 3)]
+    pub max_retries: u32,
+
+    /// Directory to cache fetched episode pages in, so re-crawls don't hit j-archive again
+    #[arg(long = "cache-dir")]
+    pub cache_dir: Option<String>,
+
+    /// Gzip cached episode pages
+    #[arg(long = "compress-cache", requires = "cache_dir")]
+    pub compress_cache: bool,
+
+    /// The maximum age in seconds of a cached episode page before it is fetched again
+    #[arg(long = "cache-max-age", requires = "cache_dir")]
+    pub cache_max_age: Option<u64>,
+
+    /// Ignore cached episode pages and overwrite them with freshly fetched copies
+    #[arg(long = "refresh", requires = "cache_dir")]
+    pub refresh: bool,
+
+    /// Record the outcome of every episode in this manifest file as the crawl progresses
+    #[arg(long = "manifest")]
+    pub manifest: Option<String>,
+
+    /// Resume the crawl recorded in this manifest file, skipping episodes that already finished
+    #[arg(long = "resume", conflicts_with_all = ["episode_no", "iterations", "season", "all_seasons", "from", "to", "latest", "since_last_run", "player", "manifest"])]
+    pub resume: Option<String>,
+
+    /// Download the pictures, audio and video clues link to into this directory
+    #[arg(long = "download-media")]
+    pub download_media: Option<PathBuf>,
+
+    /// The j-archive url to crawl (useful for mirrors and local test servers)
+    #[arg(long = "base-url", default_value = "https://j-archive.com")]
+    pub base_url: String,
+
+    /// Where to write the results to
+    #[arg(short = 'o', long = "outfile", global = true)]
+    pub outfile: Option<String>,
+
+    /// The format to write the results in
+    #[arg(short = 'f', long = "format", value_enum, default_value_t = OutputFormat::Json, global = true)]
+    pub format: OutputFormat,
+
+    /// How much of the formatting in prompts and answers to keep
+    #[arg(long = "text-format", value_enum, default_value_t = TextFormat::Plain, global = true)]
+    pub text_format: TextFormat,
+
+    /// Include each contestant's score after every clue, warning about any that disagree with j-archive
+    #[arg(long = "score-progression", global = true)]
+    pub score_progression: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Parse saved showgame.php pages (or directories of them) instead of crawling j-archive
+    Parse {
+        /// The pages or directories to parse
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
+}
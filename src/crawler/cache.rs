@@ -0,0 +1,229 @@
+use std::{
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use sha2::{Digest, Sha256};
+
+use crate::models::error::BuildError;
+
+/// On-disk cache of fetched pages, keyed by a hash of the page url
+pub struct HtmlCache {
+    dir: PathBuf,
+    compress: bool,
+    max_age: Option<Duration>,
+    refresh: bool,
+}
+
+impl HtmlCache {
+    /// Looks up a cached page, returning `None` if it is missing, expired or a refresh was requested
+    pub async fn get(&self, url: &str) -> Option<String> {
+        if self.refresh {
+            return None;
+        }
+
+        for (path, compressed) in [(self.path(url, true), true), (self.path(url, false), false)] {
+            let Ok(metadata) = tokio::fs::metadata(&path).await else {
+                continue;
+            };
+
+            if self.is_expired(metadata.modified().ok()) {
+                return None;
+            }
+
+            let bytes = tokio::fs::read(&path).await.ok()?;
+
+            return if compressed {
+                let mut html = String::new();
+                GzDecoder::new(bytes.as_slice())
+                    .read_to_string(&mut html)
+                    .ok()?;
+
+                Some(html)
+            } else {
+                String::from_utf8(bytes).ok()
+            };
+        }
+
+        None
+    }
+
+    /// Stores a page in the cache
+    pub async fn put(&self, url: &str, html: &str) -> Result<(), io::Error> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+
+        let bytes = if self.compress {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(html.as_bytes())?;
+            encoder.finish()?
+        } else {
+            html.as_bytes().to_vec()
+        };
+
+        // write to a temporary file first so concurrent readers never see a partial page
+        let path = self.path(url, self.compress);
+        let tmp_path = path.with_extension("tmp");
+
+        tokio::fs::write(&tmp_path, bytes).await?;
+        tokio::fs::rename(&tmp_path, &path).await?;
+
+        // don't leave a stale copy in the other format behind
+        match tokio::fs::remove_file(self.path(url, !self.compress)).await {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether a cache entry last modified at the given time is too old to use
+    fn is_expired(&self, modified: Option<SystemTime>) -> bool {
+        let Some(max_age) = self.max_age else {
+            return false;
+        };
+
+        match modified.and_then(|modified| modified.elapsed().ok()) {
+            Some(age) => age > max_age,
+            None => false,
+        }
+    }
+
+    /// Path of the cache entry for a url
+    fn path(&self, url: &str, compressed: bool) -> PathBuf {
+        let key = format!("{:x}", Sha256::digest(url.as_bytes()));
+        let extension = if compressed { "html.gz" } else { "html" };
+
+        self.dir.join(format!("{0}.{1}", key, extension))
+    }
+}
+
+#[derive(Default)]
+pub struct HtmlCacheBuilder {
+    dir: Option<PathBuf>,
+    compress: bool,
+    max_age: Option<Duration>,
+    refresh: bool,
+}
+
+impl HtmlCacheBuilder {
+    /// Creates a new instance of the cache builder
+    pub fn new() -> Self {
+        HtmlCacheBuilder::default()
+    }
+
+    /// Sets the directory pages are cached in
+    pub fn set_dir(&mut self, dir: impl AsRef<Path>) -> &mut Self {
+        self.dir = Some(dir.as_ref().to_path_buf());
+
+        self
+    }
+
+    /// Sets whether pages are gzip compressed on disk
+    pub fn set_compress(&mut self, compress: bool) -> &mut Self {
+        self.compress = compress;
+
+        self
+    }
+
+    /// Sets how old a cached page may be before it is fetched again
+    pub fn set_max_age(&mut self, max_age: Option<Duration>) -> &mut Self {
+        self.max_age = max_age;
+
+        self
+    }
+
+    /// Sets whether cached pages are ignored (but still overwritten with fresh copies)
+    pub fn set_refresh(&mut self, refresh: bool) -> &mut Self {
+        self.refresh = refresh;
+
+        self
+    }
+
+    /// Builds the cache
+    pub fn build(&mut self) -> Result<HtmlCache, BuildError> {
+        let Some(dir) = &self.dir else {
+            return Err(BuildError::Missing("cache dir"));
+        };
+
+        Ok(HtmlCache {
+            dir: dir.to_owned(),
+            compress: self.compress,
+            max_age: self.max_age,
+            refresh: self.refresh,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::HtmlCacheBuilder;
+
+    const URL: &str = "https://j-archive.com/showgame.php?game_id=7515";
+
+    #[tokio::test]
+    async fn round_trips_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = HtmlCacheBuilder::new().set_dir(dir.path()).build().unwrap();
+
+        assert_eq!(cache.get(URL).await, None);
+
+        cache.put(URL, "<html>7515</html>").await.unwrap();
+
+        assert_eq!(cache.get(URL).await.as_deref(), Some("<html>7515</html>"));
+        assert!(cache.path(URL, false).exists());
+    }
+
+    #[tokio::test]
+    async fn round_trips_compressed_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = HtmlCacheBuilder::new()
+            .set_dir(dir.path())
+            .set_compress(true)
+            .build()
+            .unwrap();
+
+        cache.put(URL, "<html>7515</html>").await.unwrap();
+
+        assert_eq!(cache.get(URL).await.as_deref(), Some("<html>7515</html>"));
+        assert!(cache.path(URL, true).exists());
+        assert!(!cache.path(URL, false).exists());
+    }
+
+    #[tokio::test]
+    async fn ignores_expired_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = HtmlCacheBuilder::new()
+            .set_dir(dir.path())
+            .set_max_age(Some(Duration::ZERO))
+            .build()
+            .unwrap();
+
+        cache.put(URL, "<html>7515</html>").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert_eq!(cache.get(URL).await, None);
+    }
+
+    #[tokio::test]
+    async fn ignores_pages_when_refreshing() {
+        let dir = tempfile::tempdir().unwrap();
+
+        HtmlCacheBuilder::new()
+            .set_dir(dir.path())
+            .build()
+            .unwrap()
+            .put(URL, "<html>7515</html>")
+            .await
+            .unwrap();
+
+        let cache = HtmlCacheBuilder::new()
+            .set_dir(dir.path())
+            .set_refresh(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(cache.get(URL).await, None);
+    }
+}
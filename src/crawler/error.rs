@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+/// Error raised when an episode could not be crawled
+#[derive(thiserror::Error, Debug)]
+#[error("Failed to crawl episode {episode_no} after {attempts} attempt(s): {cause}")]
+pub struct CrawlerError {
+    pub episode_no: u32,
+    pub attempts: u32,
+    #[source]
+    pub cause: CrawlerErrorCause,
+}
+
+/// The underlying reason an episode could not be crawled
+#[derive(thiserror::Error, Debug)]
+pub enum CrawlerErrorCause {
+    /// The request could not be sent or the response body could not be read
+    #[error("Request failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    /// j-archive responded with a non-success status code
+    #[error("Received status {status}")]
+    Status {
+        status: StatusCode,
+        retry_after: Option<Duration>,
+    },
+
+    /// j-archive has no game with the requested id
+    #[error("Episode is missing from the j-archive database")]
+    MissingEpisode,
+
+    /// j-archive has no player with the requested id
+    #[error("Player is missing from the j-archive database")]
+    MissingPlayer,
+
+    /// A downloaded file could not be saved
+    #[error("Could not save file: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The page was fetched but could not be written to the cache
+    #[error("Could not cache page: {0}")]
+    Cache(#[source] std::io::Error),
+
+    /// The page was fetched but could not be parsed
+    #[error("Could not parse episode: {0}")]
+    Parse(#[from] crate::parser::ParserError),
+
+    /// Crawling the episode panicked
+    #[error("Crawling the episode panicked: {0}")]
+    Panicked(#[source] tokio::task::JoinError),
+}
+
+impl CrawlerErrorCause {
+    /// Whether retrying the same request might succeed
+    pub fn is_transient(&self) -> bool {
+        match self {
+            CrawlerErrorCause::Request(err) => {
+                err.is_timeout() || !(err.is_builder() || err.is_redirect())
+            }
+            CrawlerErrorCause::Status { status, .. } => {
+                status.is_server_error()
+                    || *status == StatusCode::TOO_MANY_REQUESTS
+                    || *status == StatusCode::REQUEST_TIMEOUT
+            }
+            CrawlerErrorCause::MissingEpisode
+            | CrawlerErrorCause::MissingPlayer
+            | CrawlerErrorCause::Io(_)
+            | CrawlerErrorCause::Cache(_)
+            | CrawlerErrorCause::Parse(_)
+            | CrawlerErrorCause::Panicked(_) => false,
+        }
+    }
+
+    /// How long the server asked us to wait before retrying, if it said
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            CrawlerErrorCause::Status { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
@@ -1,91 +1,1119 @@
+mod cache;
+mod error;
+mod progress;
+mod rate_limiter;
+mod retry;
+
 use crate::models::episode::JeopardyEpisode;
-use crate::parser::JArchiveDocumentParser;
-use crate::reporter::ReporterBuilder;
-use std::error::Error;
-use std::fmt;
+use crate::models::error::BuildError;
+use crate::models::media::MediaRef;
+use crate::models::player::Player;
+use crate::models::season::{Season, SeasonGame};
+use crate::parser::{
+    parse_player, parse_season_games, parse_seasons, JArchiveDocumentParser, TextFormat,
+};
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::{JoinError, JoinHandle, JoinSet};
 
-#[derive(Default)]
-pub struct JArchiveCrawler;
+pub use cache::{HtmlCache, HtmlCacheBuilder};
+pub use error::{CrawlerError, CrawlerErrorCause};
+pub use rate_limiter::RateLimiter;
+pub use retry::RetryPolicy;
+
+const DEFAULT_BASE_URL: &str = "https://j-archive.com";
+const DEFAULT_REQUESTS_PER_SECOND: f64 = 2.0;
+const DEFAULT_BURST: u32 = 4;
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How many episodes, per worker, a crawl may run ahead of the oldest episode it hasn't sent yet
+const LOOKAHEAD_PER_WORKER: usize = 4;
+
+/// Fetches and parses episodes from j-archive
+///
+/// ```no_run
+/// use j_archive_crawler::crawler::JArchiveCrawlerBuilder;
+///
+/// # async fn run() {
+/// let crawler = JArchiveCrawlerBuilder::new()
+///     .set_concurrency(4)
+///     .set_requests_per_second(1.0)
+///     .build()
+///     .unwrap();
+///
+/// let mut results = crawler.crawl(vec![7515, 7516]);
+///
+/// while let Some(result) = results.recv().await {
+///     match result {
+///         Ok(episode) => println!("{0}: {1:?}", episode.id, episode.air_date),
+///         Err(err) => eprintln!("{0}", err),
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct JArchiveCrawler {
+    client: reqwest::Client,
+    base_url: String,
+    /// The base url with a trailing slash, which relative media links are resolved against
+    media_base_url: reqwest::Url,
+    concurrency: usize,
+    report_progress: bool,
+    rate_limiter: Arc<RateLimiter>,
+    retry_policy: RetryPolicy,
+    cache: Option<Arc<HtmlCache>>,
+    text_format: TextFormat,
+}
 
 impl JArchiveCrawler {
-    /// Creates a new instance of the scraper
-    pub fn new() -> Self {
-        Default::default()
+    /// Crawls j-archive for jeopardy questions
+    ///
+    /// Up to `concurrency` episodes are fetched and parsed at the same time in the background. The
+    /// outcome of every episode is sent down the returned channel in the order the episodes were
+    /// requested, as soon as it and every episode before it have finished. A slow episode holds
+    /// back at most a few episodes per worker, after which the crawl waits for it. Dropping the
+    /// returned [`Crawl`] stops the crawl.
+    pub fn crawl(self, episode_ids: Vec<u32>) -> Crawl {
+        self.crawl_with_seasons(episode_ids.into_iter().map(|id| (id, None)).collect())
     }
 
-    /// Crawls j-archive for jeopardy questions
-    pub async fn crawl(
+    /// Crawls episodes like [`JArchiveCrawler::crawl`], filling in the season each one was listed
+    /// under
+    ///
+    /// Each episode is given as its id along with its season's id, if it is known.
+    pub fn crawl_with_seasons(self, episodes: Vec<(u32, Option<String>)>) -> Crawl {
+        let (sender, receiver) = mpsc::channel(self.concurrency);
+
+        let handle = tokio::spawn(self.run(episodes, sender));
+
+        Crawl { receiver, handle }
+    }
+
+    /// Drives a crawl, sending results in request order
+    async fn run(
         self,
-        episode_no: u32,
-        iterations: u32,
-    ) -> Result<Vec<JeopardyEpisode>, CrawlerError> {
-        let mut results: Vec<JeopardyEpisode> = Vec::new();
+        episodes: Vec<(u32, Option<String>)>,
+        sender: mpsc::Sender<Result<JeopardyEpisode, CrawlerError>>,
+    ) {
+        let total = episodes.len();
 
-        let episode_range = episode_no..(episode_no + iterations);
-        let total = episode_range.len();
-        let mut index = 0;
+        let permits = Arc::new(Semaphore::new(self.concurrency));
+        let mut tasks = JoinSet::new();
+        let mut episodes = episodes.into_iter().enumerate();
 
-        let reporter = ReporterBuilder::new()
-            .build()
-            .expect("Could not build reporter with given data");
-
-        for episode in episode_range {
-            // Write proress to stdout
-            reporter.report_progress(episode, index, total).unwrap();
-
-            // Parse raw html
-            let raw_html = JArchiveCrawler::get_html(episode).await.map_err(|_| {
-                CrawlerError::new(format!("Failed to get HTML for episode {0}", episode))
-            })?;
-
-            // See if the
-            if raw_html.contains(&format!("ERROR: No game {0} in database.", episode_no)) {
-                return Err(CrawlerError::new(format!(
-                    "Missing episode {0} in JArchive database",
-                    episode
-                )));
+        // results which finished before an earlier episode, waiting for their turn
+        let mut finished = BTreeMap::new();
+        let mut next = 0;
+        let mut completed = 0;
+
+        let lookahead = self.concurrency.saturating_mul(LOOKAHEAD_PER_WORKER);
+        let mut report_progress = self.report_progress;
+
+        loop {
+            // only start episodes within the lookahead of the oldest unsent one, so that the
+            // results waiting for it can't pile up
+            while tasks.len() + finished.len() < lookahead {
+                let Some((index, (episode, season))) = episodes.next() else {
+                    break;
+                };
+
+                let crawler = self.clone();
+                let permits = Arc::clone(&permits);
+
+                tasks.spawn(async move {
+                    let _permit = permits
+                        .acquire_owned()
+                        .await
+                        .expect("Crawler semaphore was closed");
+
+                    (
+                        index,
+                        episode,
+                        crawler.crawl_isolated(episode, season).await,
+                    )
+                });
             }
 
-            let document = scraper::Html::parse_document(&raw_html);
+            let Some(joined) = tasks.join_next().await else {
+                break;
+            };
+
+            // the task only waits for a permit and then for the isolated crawl, so it can't panic
+            let (index, episode, result) = joined.expect("Crawler task panicked");
+
+            // Write progress to stderr, keeping stdout free for the results. The spinner is only
+            // cosmetic, so it's given up on rather than stopping the crawl if it can't be drawn
+            if report_progress {
+                report_progress = progress::report_progress(episode, completed, total).is_ok();
+            }
+            completed += 1;
+
+            finished.insert(index, result);
+
+            while let Some(result) = finished.remove(&next) {
+                next += 1;
+
+                if sender.send(result).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Crawls a single episode in its own task, so that a panic while parsing it fails just that
+    /// episode rather than the whole crawl
+    async fn crawl_isolated(
+        &self,
+        episode_no: u32,
+        season: Option<String>,
+    ) -> Result<JeopardyEpisode, CrawlerError> {
+        let crawler = self.clone();
+
+        let result = tokio::spawn(async move { crawler.crawl_episode(episode_no).await }).await;
+
+        match result {
+            Ok(result) => result.map(|mut episode| {
+                episode.season = season;
 
-            if let Ok(episode_data) = JArchiveDocumentParser::new(document, episode).parse() {
-                results.push(episode_data);
-            } else {
-                println!(
-                    "Failed to scrape j-archive.com for jeopardy episode {0}",
-                    episode
-                )
+                episode
+            }),
+            Err(err) => Err(CrawlerError {
+                episode_no,
+                attempts: 1,
+                cause: CrawlerErrorCause::Panicked(err),
+            }),
+        }
+    }
+
+    /// Fetches and parses a single episode
+    async fn crawl_episode(&self, episode_no: u32) -> Result<JeopardyEpisode, CrawlerError> {
+        let (raw_html, attempts) = self.get_html_with_retries(episode_no).await?;
+
+        let error = |cause: CrawlerErrorCause| CrawlerError {
+            episode_no,
+            attempts,
+            cause,
+        };
+
+        // See if the episode exists
+        if is_missing_episode(&raw_html, episode_no) {
+            return Err(error(CrawlerErrorCause::MissingEpisode));
+        }
+
+        let document = scraper::Html::parse_document(&raw_html);
+
+        JArchiveDocumentParser::new(document, episode_no)
+            .set_text_format(self.text_format)
+            .set_base_url(self.media_base_url.clone())
+            .parse()
+            .map_err(|err| error(err.into()))
+    }
+
+    /// Gets the raw html for an episode, retrying transient failures with jittered exponential backoff
+    ///
+    /// Returns the html along with the number of attempts it took to fetch it.
+    async fn get_html_with_retries(&self, episode_no: u32) -> Result<(String, u32), CrawlerError> {
+        self.with_retries(|| self.get_html(episode_no))
+            .await
+            .map_err(|(cause, attempts)| CrawlerError {
+                episode_no,
+                attempts,
+                cause,
+            })
+    }
+
+    /// Runs a request until it succeeds, fails permanently or runs out of retries
+    ///
+    /// Returns the response along with the number of attempts it took, or the last error along
+    /// with the number of attempts made.
+    async fn with_retries<T, F, Fut>(
+        &self,
+        request: F,
+    ) -> Result<(T, u32), (CrawlerErrorCause, u32)>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, CrawlerErrorCause>>,
+    {
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+
+            let cause = match request().await {
+                Ok(response) => return Ok((response, attempts)),
+                Err(cause) => cause,
             };
 
-            index = index + 1;
+            if !cause.is_transient() || attempts > self.retry_policy.max_retries {
+                return Err((cause, attempts));
+            }
+
+            // don't let the server hold a worker for longer than we'd ever back off
+            let delay = cause
+                .retry_after()
+                .map(|retry_after| retry_after.min(self.retry_policy.max_delay))
+                .unwrap_or_else(|| self.retry_policy.backoff(attempts));
+
+            tokio::time::sleep(delay).await;
         }
+    }
+
+    /// Lists every season on j-archive, oldest first
+    pub async fn seasons(&self) -> Result<Vec<Season>, CrawlerErrorCause> {
+        let raw_html = self.get_index_page("listseasons.php").await?;
+
+        Ok(parse_seasons(&raw_html))
+    }
+
+    /// Lists the seasons with any games that aired between two dates (inclusive), oldest first
+    pub async fn seasons_aired_between(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<Season>, CrawlerErrorCause> {
+        let seasons = self.seasons().await?;
+
+        Ok(seasons
+            .into_iter()
+            .filter(|season| season.aired_between(from, to))
+            .collect())
+    }
+
+    /// Finds the newest game on j-archive, along with the season it belongs to
+    pub async fn latest_game(&self) -> Result<Option<(Season, SeasonGame)>, CrawlerErrorCause> {
+        let Some(season) = self.seasons().await?.pop() else {
+            return Ok(None);
+        };
+
+        let game = self.season_games(&season.id).await?.pop();
 
-        Ok(results)
+        Ok(game.map(|game| (season, game)))
     }
 
-    /// Gets the raw html for a page
-    pub async fn get_html(episode_no: u32) -> Result<String, Box<dyn Error>> {
-        let url = format!("https://j-archive.com/showgame.php?game_id={0}", episode_no);
+    /// Lists the games j-archive has for a season, in the order they aired
+    pub async fn season_games(&self, season: &str) -> Result<Vec<SeasonGame>, CrawlerErrorCause> {
+        let raw_html = self
+            .get_index_page(&format!("showseason.php?season={0}", season))
+            .await?;
+
+        Ok(parse_season_games(&raw_html))
+    }
+
+    /// Fetches a player's profile, including every game they played in
+    pub async fn player(&self, player_id: u32) -> Result<Player, CrawlerErrorCause> {
+        let raw_html = self
+            .get_index_page(&format!("showplayer.php?player_id={0}", player_id))
+            .await?;
+
+        parse_player(&raw_html, player_id).ok_or(CrawlerErrorCause::MissingPlayer)
+    }
+
+    /// Fetches one of j-archive's index pages
+    ///
+    /// These change whenever a new game is archived, so they are never cached.
+    async fn get_index_page(&self, page: &str) -> Result<String, CrawlerErrorCause> {
+        let url = format!("{0}/{1}", self.base_url, page);
+
+        self.with_retries(|| self.fetch(&url))
+            .await
+            .map(|(raw_html, _)| raw_html)
+            .map_err(|(cause, _)| cause)
+    }
 
-        let raw_html = reqwest::get(url).await?.text().await?;
+    /// Gets the raw html for a page, preferring the on-disk cache when one is configured
+    pub async fn get_html(&self, episode_no: u32) -> Result<String, CrawlerErrorCause> {
+        let url = format!("{0}/showgame.php?game_id={1}", self.base_url, episode_no);
+
+        let Some(cache) = &self.cache else {
+            return self.fetch(&url).await;
+        };
+
+        if let Some(raw_html) = cache.get(&url).await {
+            return Ok(raw_html);
+        }
+
+        let raw_html = self.fetch(&url).await?;
+
+        // missing episodes may be added to j-archive later, so don't remember them
+        if !is_missing_episode(&raw_html, episode_no) {
+            cache
+                .put(&url, &raw_html)
+                .await
+                .map_err(CrawlerErrorCause::Cache)?;
+        }
 
         Ok(raw_html)
     }
+
+    /// Downloads a clue's media into a directory, under a file name that is stable between crawls
+    ///
+    /// Media that has already been downloaded isn't fetched again. Returns where the file is.
+    pub async fn download_media(
+        &self,
+        media: &MediaRef,
+        dir: &Path,
+    ) -> Result<PathBuf, CrawlerErrorCause> {
+        let file_name = media.file_name();
+        let path = dir.join(&file_name);
+
+        if tokio::fs::metadata(&path).await.is_ok() {
+            return Ok(path);
+        }
+
+        let (bytes, _) = self
+            .with_retries(|| self.fetch_bytes(&media.url))
+            .await
+            .map_err(|(cause, _)| cause)?;
+
+        // write to a temporary file first so an interrupted download isn't mistaken for a
+        // finished one
+        let partial_path = dir.join(format!("{0}.part", file_name));
+        tokio::fs::create_dir_all(dir).await?;
+        tokio::fs::write(&partial_path, bytes).await?;
+        tokio::fs::rename(&partial_path, &path).await?;
+
+        Ok(path)
+    }
+
+    /// Fetches a url once the rate limiter allows another request to its host
+    async fn fetch(&self, url: &str) -> Result<String, CrawlerErrorCause> {
+        Ok(self.send(url).await?.text().await?)
+    }
+
+    /// Fetches the raw bytes at a url, for media that isn't text
+    async fn fetch_bytes(&self, url: &str) -> Result<Vec<u8>, CrawlerErrorCause> {
+        Ok(self.send(url).await?.bytes().await?.to_vec())
+    }
+
+    /// Sends a request once the rate limiter allows another request to its host, failing on
+    /// unsuccessful statuses
+    async fn send(&self, url: &str) -> Result<reqwest::Response, CrawlerErrorCause> {
+        if let Some(host) = reqwest::Url::parse(url)
+            .ok()
+            .as_ref()
+            .and_then(|url| url.host_str())
+        {
+            self.rate_limiter.acquire(host).await;
+        }
+
+        let response = self.client.get(url).send().await?;
+        let status = response.status();
+
+        if !status.is_success() {
+            return Err(CrawlerErrorCause::Status {
+                status,
+                retry_after: retry::parse_retry_after(response.headers()),
+            });
+        }
+
+        Ok(response)
+    }
 }
 
-#[derive(Debug, Clone)]
-pub struct CrawlerError {
-    msg: String,
+/// A crawl running in the background, see [`JArchiveCrawler::crawl`]
+pub struct Crawl {
+    receiver: mpsc::Receiver<Result<JeopardyEpisode, CrawlerError>>,
+    handle: JoinHandle<()>,
 }
 
-impl fmt::Display for CrawlerError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.msg)
+impl Crawl {
+    /// Waits for the outcome of the next episode, or `None` once the crawl has stopped
+    pub async fn recv(&mut self) -> Option<Result<JeopardyEpisode, CrawlerError>> {
+        self.receiver.recv().await
+    }
+
+    /// Waits for the crawl to stop, failing if it was aborted before every episode was sent
+    pub async fn finish(self) -> Result<(), JoinError> {
+        drop(self.receiver);
+
+        self.handle.await
+    }
+}
+
+/// Whether j-archive responded with its "no such game" page
+fn is_missing_episode(raw_html: &str, episode_no: u32) -> bool {
+    raw_html.contains(&format!("ERROR: No game {0} in database.", episode_no))
+}
+
+#[derive(Default)]
+pub struct JArchiveCrawlerBuilder {
+    base_url: Option<String>,
+    concurrency: Option<usize>,
+    requests_per_second: Option<f64>,
+    burst: Option<u32>,
+    retry_policy: Option<RetryPolicy>,
+    timeout: Option<Duration>,
+    cache: Option<HtmlCache>,
+    report_progress: bool,
+    text_format: Option<TextFormat>,
+}
+
+impl JArchiveCrawlerBuilder {
+    /// Creates a new instance of the crawler builder
+    pub fn new() -> Self {
+        JArchiveCrawlerBuilder::default()
+    }
+
+    /// Sets the maximum number of episodes fetched at the same time
+    pub fn set_concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = Some(concurrency);
+
+        self
+    }
+
+    /// Sets whether a progress spinner is drawn on stderr while crawling
+    pub fn set_report_progress(&mut self, report_progress: bool) -> &mut Self {
+        self.report_progress = report_progress;
+
+        self
+    }
+
+    /// Sets the j-archive url that pages are fetched from
+    pub fn set_base_url(&mut self, base_url: impl Into<String>) -> &mut Self {
+        self.base_url = Some(base_url.into());
+
+        self
+    }
+
+    /// Sets the number of requests per second allowed to a single host
+    pub fn set_requests_per_second(&mut self, requests_per_second: f64) -> &mut Self {
+        self.requests_per_second = Some(requests_per_second);
+
+        self
+    }
+
+    /// Sets the number of requests that may be sent to a host at once before rate limiting kicks in
+    pub fn set_burst(&mut self, burst: u32) -> &mut Self {
+        self.burst = Some(burst);
+
+        self
+    }
+
+    /// Sets how transient fetch failures are retried
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) -> &mut Self {
+        self.retry_policy = Some(retry_policy);
+
+        self
+    }
+
+    /// Sets how long a single request may take, from connecting to reading the whole body
+    ///
+    /// Requests that time out are retried like any other transient failure.
+    pub fn set_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+
+        self
+    }
+
+    /// Sets the on-disk cache consulted before fetching episode pages
+    pub fn set_cache(&mut self, cache: Option<HtmlCache>) -> &mut Self {
+        self.cache = cache;
+
+        self
+    }
+
+    /// Sets how much of the formatting in prompts and answers is kept
+    pub fn set_text_format(&mut self, text_format: TextFormat) -> &mut Self {
+        self.text_format = Some(text_format);
+
+        self
+    }
+
+    /// Builds the crawler
+    pub fn build(&mut self) -> Result<JArchiveCrawler, BuildError> {
+        let concurrency = self.concurrency.unwrap_or(1);
+        let requests_per_second = self
+            .requests_per_second
+            .unwrap_or(DEFAULT_REQUESTS_PER_SECOND);
+
+        if concurrency == 0 {
+            return Err(BuildError::ZeroConcurrency);
+        }
+
+        if requests_per_second <= 0.0 || !requests_per_second.is_finite() {
+            return Err(BuildError::InvalidRequestsPerSecond(requests_per_second));
+        }
+
+        let timeout = self.timeout.unwrap_or(DEFAULT_TIMEOUT);
+
+        let client = reqwest::Client::builder()
+            .connect_timeout(DEFAULT_CONNECT_TIMEOUT.min(timeout))
+            .timeout(timeout)
+            .build()
+            .map_err(BuildError::HttpClient)?;
+
+        let base_url = self
+            .base_url
+            .to_owned()
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+            .trim_end_matches('/')
+            .to_string();
+        let media_base_url = reqwest::Url::parse(&format!("{0}/", base_url)).map_err(|source| {
+            BuildError::InvalidBaseUrl {
+                url: base_url.clone(),
+                source,
+            }
+        })?;
+
+        Ok(JArchiveCrawler {
+            client,
+            base_url,
+            media_base_url,
+            concurrency,
+            report_progress: self.report_progress,
+            rate_limiter: Arc::new(RateLimiter::new(
+                requests_per_second,
+                self.burst.unwrap_or(DEFAULT_BURST),
+            )),
+            retry_policy: self.retry_policy.unwrap_or_default(),
+            cache: self.cache.take().map(Arc::new),
+            text_format: self.text_format.unwrap_or_default(),
+        })
     }
 }
 
-impl CrawlerError {
-    pub fn new(msg: impl Into<String>) -> CrawlerError {
-        CrawlerError { msg: msg.into() }
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use chrono::NaiveDate;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use wiremock::matchers::query_param;
+
+    use super::{
+        Crawl, CrawlerError, CrawlerErrorCause, HtmlCacheBuilder, JArchiveCrawler,
+        JArchiveCrawlerBuilder, RetryPolicy,
+    };
+    use crate::models::{episode::JeopardyEpisode, error::BuildError, media::MediaRef};
+
+    const FIXTURE: &str = include_str!("../../fixtures/synthetic_game.html");
+
+    /// Waits for a crawl to finish, returning every result
+    async fn collect(mut crawl: Crawl) -> Vec<Result<JeopardyEpisode, CrawlerError>> {
+        let mut results = Vec::new();
+
+        while let Some(result) = crawl.recv().await {
+            results.push(result);
+        }
+
+        crawl.finish().await.unwrap();
+
+        results
+    }
+
+    /// Builds a crawler pointed at the mock server which retries quickly
+    fn test_crawler(server: &MockServer) -> JArchiveCrawler {
+        JArchiveCrawlerBuilder::new()
+            .set_base_url(server.uri())
+            .set_requests_per_second(1000.0)
+            .set_retry_policy(RetryPolicy {
+                max_retries: 2,
+                base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(10),
+            })
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        assert!(matches!(
+            JArchiveCrawlerBuilder::new().set_concurrency(0).build(),
+            Err(BuildError::ZeroConcurrency)
+        ));
+        assert!(matches!(
+            JArchiveCrawlerBuilder::new()
+                .set_requests_per_second(f64::NAN)
+                .build(),
+            Err(BuildError::InvalidRequestsPerSecond(_))
+        ));
+        assert!(matches!(
+            JArchiveCrawlerBuilder::new()
+                .set_base_url("j-archive")
+                .build(),
+            Err(BuildError::InvalidBaseUrl { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn rate_limits_concurrent_fetches() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/showgame.php"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html></html>"))
+            .expect(6)
+            .mount(&server)
+            .await;
+
+        let crawler = JArchiveCrawlerBuilder::new()
+            .set_base_url(server.uri())
+            .set_concurrency(6)
+            .set_requests_per_second(10.0)
+            .set_burst(2)
+            .build()
+            .unwrap();
+
+        let started = Instant::now();
+
+        // two requests go out immediately, the remaining four are spaced 100ms apart
+        collect(crawler.crawl((1..=6).collect())).await;
+
+        assert!(started.elapsed() >= Duration::from_millis(350));
+    }
+
+    #[tokio::test]
+    async fn retries_transient_failures() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html></html>"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let (_, attempts) = test_crawler(&server)
+            .get_html_with_retries(1)
+            .await
+            .unwrap();
+
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let err = test_crawler(&server)
+            .get_html_with_retries(42)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.episode_no, 42);
+        assert_eq!(err.attempts, 3);
+        assert_eq!(err.cause.retry_after(), Some(Duration::ZERO));
+    }
+
+    #[tokio::test]
+    async fn caps_retry_after_at_max_delay() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503).insert_header("Retry-After", "3600"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html></html>"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let started = Instant::now();
+
+        let (_, attempts) = test_crawler(&server)
+            .get_html_with_retries(1)
+            .await
+            .unwrap();
+
+        assert_eq!(attempts, 2);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn retries_timed_out_requests() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("<html></html>")
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html></html>"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let crawler = JArchiveCrawlerBuilder::new()
+            .set_base_url(server.uri())
+            .set_requests_per_second(1000.0)
+            .set_timeout(Duration::from_millis(100))
+            .set_retry_policy(RetryPolicy {
+                max_retries: 2,
+                base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(10),
+            })
+            .build()
+            .unwrap();
+
+        let (_, attempts) = crawler.get_html_with_retries(1).await.unwrap();
+
+        assert_eq!(attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_not_found() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let err = test_crawler(&server)
+            .get_html_with_retries(1)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.attempts, 1);
+        assert!(matches!(err.cause, CrawlerErrorCause::Status { .. }));
+    }
+
+    #[tokio::test]
+    async fn skips_missing_episodes() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("<p>ERROR: No game 9999 in database.</p>"),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let crawler = test_crawler(&server);
+        let err = crawler.crawl_episode(9999).await.unwrap_err();
+
+        assert!(matches!(err.cause, CrawlerErrorCause::MissingEpisode));
+        let results = collect(crawler.crawl(vec![9999])).await;
+
+        assert!(matches!(
+            results[..],
+            [Err(CrawlerError {
+                cause: CrawlerErrorCause::MissingEpisode,
+                ..
+            })]
+        ));
+    }
+
+    #[tokio::test]
+    async fn serves_cached_pages_without_fetching() {
+        let server = MockServer::start().await;
+        let dir = tempfile::tempdir().unwrap();
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html>7515</html>"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let crawler = JArchiveCrawlerBuilder::new()
+            .set_base_url(server.uri())
+            .set_cache(Some(
+                HtmlCacheBuilder::new().set_dir(dir.path()).build().unwrap(),
+            ))
+            .build()
+            .unwrap();
+
+        assert_eq!(crawler.get_html(7515).await.unwrap(), "<html>7515</html>");
+        assert_eq!(crawler.get_html(7515).await.unwrap(), "<html>7515</html>");
+    }
+
+    #[tokio::test]
+    async fn reports_pages_that_could_not_be_cached() {
+        let server = MockServer::start().await;
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("not-a-dir");
+
+        std::fs::write(&file, "").unwrap();
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html>7515</html>"))
+            .mount(&server)
+            .await;
+
+        let crawler = JArchiveCrawlerBuilder::new()
+            .set_base_url(server.uri())
+            .set_cache(Some(
+                HtmlCacheBuilder::new().set_dir(&file).build().unwrap(),
+            ))
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            crawler.get_html(7515).await,
+            Err(CrawlerErrorCause::Cache(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn sends_results_in_request_order() {
+        let server = MockServer::start().await;
+
+        // the first episode finishes last
+        Mock::given(method("GET"))
+            .and(query_param("game_id", "1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(FIXTURE)
+                    .set_delay(Duration::from_millis(300)),
+            )
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(FIXTURE))
+            .mount(&server)
+            .await;
+
+        let crawler = JArchiveCrawlerBuilder::new()
+            .set_base_url(server.uri())
+            .set_concurrency(4)
+            .set_requests_per_second(1000.0)
+            .build()
+            .unwrap();
+
+        let ids = collect(crawler.crawl(vec![1, 2, 3, 4]))
+            .await
+            .into_iter()
+            .map(|result| result.unwrap().id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn waits_for_slow_episodes_instead_of_running_ahead() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(query_param("game_id", "1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("<html></html>")
+                    .set_delay(Duration::from_millis(1000)),
+            )
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html></html>"))
+            .mount(&server)
+            .await;
+
+        let crawler = JArchiveCrawlerBuilder::new()
+            .set_base_url(server.uri())
+            .set_concurrency(2)
+            .set_requests_per_second(1000.0)
+            .build()
+            .unwrap();
+
+        let receiver = crawler.crawl((1..=40).collect());
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        // while the first episode is outstanding, only the lookahead of 4 per worker is fetched
+        let fetched = server.received_requests().await.unwrap().len();
+        assert!(fetched <= 8, "fetched {0} episodes", fetched);
+
+        assert_eq!(collect(receiver).await.len(), 40);
+    }
+
+    #[tokio::test]
+    async fn fills_in_episode_seasons() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(FIXTURE))
+            .mount(&server)
+            .await;
+
+        let seasons = collect(
+            test_crawler(&server)
+                .crawl_with_seasons(vec![(7515, Some("39".to_string())), (7516, None)]),
+        )
+        .await
+        .into_iter()
+        .map(|result| result.unwrap().season)
+        .collect::<Vec<_>>();
+
+        assert_eq!(seasons, vec![Some("39".to_string()), None]);
+    }
+
+    #[tokio::test]
+    async fn lists_season_games() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/listseasons.php"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(include_str!("../../fixtures/synthetic_seasons.html")),
+            )
+            .mount(&server)
+            .await;
+
+        // the season page fails once before succeeding
+        Mock::given(method("GET"))
+            .and(path("/showseason.php"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/showseason.php"))
+            .and(query_param("season", "39"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(include_str!("../../fixtures/synthetic_season.html")),
+            )
+            .mount(&server)
+            .await;
+
+        let crawler = test_crawler(&server);
+
+        let seasons = crawler.seasons().await.unwrap();
+        assert_eq!(seasons.last().unwrap().id, "39");
+
+        let games = crawler.season_games("39").await.unwrap();
+        assert_eq!(games.len(), 5);
+        assert_eq!(games[0].game_id, 7467);
+
+        let (season, game) = crawler.latest_game().await.unwrap().unwrap();
+        assert_eq!(season.id, "39");
+        assert_eq!(game.game_id, 7517);
+    }
+
+    #[tokio::test]
+    async fn fetches_player_profiles() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/showplayer.php"))
+            .and(query_param("player_id", "13403"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(include_str!("../../fixtures/synthetic_player.html")),
+            )
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/showplayer.php"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html></html>"))
+            .mount(&server)
+            .await;
+
+        let crawler = test_crawler(&server);
+
+        let player = crawler.player(13403).await.unwrap();
+        assert_eq!(player.episode_ids(), vec![7515, 7516, 7517]);
+
+        let err = crawler.player(1).await.unwrap_err();
+        assert!(matches!(err, CrawlerErrorCause::MissingPlayer));
+    }
+
+    #[tokio::test]
+    async fn downloads_media_once() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/media/2022-11-17_J_11.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0xff, 0xd8, 0xff]))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/clips/"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![1, 2, 3]))
+            .mount(&server)
+            .await;
+
+        let crawler = test_crawler(&server);
+        let dir = tempfile::tempdir().unwrap();
+
+        let picture = MediaRef::new(format!("{0}/media/2022-11-17_J_11.jpg", server.uri()));
+        for _ in 0..2 {
+            let path = crawler.download_media(&picture, dir.path()).await.unwrap();
+
+            assert_eq!(path, dir.path().join(picture.file_name()));
+            assert_eq!(std::fs::read(&path).unwrap(), vec![0xff, 0xd8, 0xff]);
+        }
+
+        // a file with the same name elsewhere doesn't replace it
+        let copy = MediaRef::new(format!("{0}/copies/2022-11-17_J_11.jpg", server.uri()));
+        assert_ne!(copy.file_name(), picture.file_name());
+        assert!(picture.file_name().ends_with("_2022-11-17_J_11.jpg"));
+
+        // urls without a usable file name are named after their hash
+        let clip = MediaRef::new(format!("{0}/clips/", server.uri()));
+        let path = crawler.download_media(&clip, dir.path()).await.unwrap();
+        assert_eq!(path.file_name().unwrap().len(), 64);
+        assert_eq!(std::fs::read(&path).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn lists_seasons_aired_between_dates() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/listseasons.php"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(include_str!("../../fixtures/synthetic_seasons.html")),
+            )
+            .mount(&server)
+            .await;
+
+        let crawler = test_crawler(&server);
+
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let seasons = crawler
+            .seasons_aired_between(date(1990, 7, 1), date(2021, 1, 1))
+            .await
+            .unwrap();
+
+        // seasons without dates can't be ruled out
+        let ids = seasons
+            .iter()
+            .map(|season| season.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["trebekpilots", "superjeopardy", "15", "37"]);
     }
 }
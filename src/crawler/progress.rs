@@ -0,0 +1,32 @@
+use std::io::{self, stderr};
+
+use crossterm::{
+    cursor::{RestorePosition, SavePosition},
+    style::{Color, Print, ResetColor, SetForegroundColor},
+    terminal::{Clear, ClearType},
+    ExecutableCommand,
+};
+
+/// Draws a spinner with the crawl's progress on stderr, so it can't end up in results written to
+/// stdout
+pub(super) fn report_progress(episode_no: u32, curr: usize, total: usize) -> Result<(), io::Error> {
+    let symbols = ["\\", "|", "/", "―"];
+    let modulo = curr.rem_euclid(symbols.len());
+
+    let output = format!(" {} ", symbols[modulo]);
+    let formatted_episode_no = format!(" #{} ({} / {})", episode_no, (curr + 1), total);
+
+    stderr()
+        .execute(SavePosition)?
+        .execute(Clear(ClearType::CurrentLine))?
+        .execute(SetForegroundColor(Color::Green))?
+        .execute(Print(output))?
+        .execute(ResetColor)?
+        .execute(Print("Crawling episode"))?
+        .execute(SetForegroundColor(Color::Green))?
+        .execute(Print(formatted_episode_no))?
+        .execute(ResetColor)?
+        .execute(RestorePosition)?;
+
+    Ok(())
+}
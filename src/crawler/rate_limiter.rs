@@ -0,0 +1,112 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Token bucket for a single host
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token bucket rate limiter which keeps a separate budget for every host, treating a `www.`
+/// host as the same host without it
+pub struct RateLimiter {
+    requests_per_second: f64,
+    burst: u32,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Creates a new rate limiter allowing `requests_per_second` requests per host, with up to `burst` requests at once
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        RateLimiter {
+            requests_per_second,
+            burst: burst.max(1),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Waits until a request to the given host is allowed
+    pub async fn acquire(&self, host: &str) {
+        if let Some(delay) = self.reserve(host) {
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Takes a token from the host's bucket, returning how long the caller must wait before using it
+    ///
+    /// The bucket is allowed to go negative so that concurrent callers queue up behind each other
+    /// instead of all waking at the same time and racing for the next token.
+    fn reserve(&self, host: &str) -> Option<Duration> {
+        // `www.j-archive.com` is the same server as `j-archive.com`, so they share a budget
+        let host = host.strip_prefix("www.").unwrap_or(host);
+
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().expect("Rate limiter lock was poisoned");
+
+        let bucket = buckets.entry(host.to_string()).or_insert(TokenBucket {
+            tokens: self.burst as f64,
+            last_refill: now,
+        });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.requests_per_second).min(self.burst as f64);
+        bucket.last_refill = now;
+        bucket.tokens -= 1.0;
+
+        if bucket.tokens >= 0.0 {
+            None
+        } else {
+            Some(Duration::from_secs_f64(
+                -bucket.tokens / self.requests_per_second,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RateLimiter;
+
+    #[test]
+    fn allows_burst_without_waiting() {
+        let limiter = RateLimiter::new(1.0, 3);
+
+        assert!(limiter.reserve("j-archive.com").is_none());
+        assert!(limiter.reserve("j-archive.com").is_none());
+        assert!(limiter.reserve("j-archive.com").is_none());
+        assert!(limiter.reserve("j-archive.com").is_some());
+    }
+
+    #[test]
+    fn queues_callers_once_burst_is_spent() {
+        let limiter = RateLimiter::new(2.0, 1);
+
+        assert!(limiter.reserve("j-archive.com").is_none());
+
+        let first = limiter.reserve("j-archive.com").unwrap();
+        let second = limiter.reserve("j-archive.com").unwrap();
+
+        assert!(first.as_secs_f64() > 0.4 && first.as_secs_f64() <= 0.5);
+        assert!(second.as_secs_f64() > 0.9 && second.as_secs_f64() <= 1.0);
+    }
+
+    #[test]
+    fn keeps_separate_budgets_per_host() {
+        let limiter = RateLimiter::new(1.0, 1);
+
+        assert!(limiter.reserve("j-archive.com").is_none());
+        assert!(limiter.reserve("example.com").is_none());
+        assert!(limiter.reserve("j-archive.com").is_some());
+    }
+
+    #[test]
+    fn shares_budget_between_www_and_bare_host() {
+        let limiter = RateLimiter::new(1.0, 1);
+
+        assert!(limiter.reserve("j-archive.com").is_none());
+        assert!(limiter.reserve("www.j-archive.com").is_some());
+    }
+}
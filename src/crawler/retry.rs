@@ -0,0 +1,123 @@
+use std::time::{Duration, SystemTime};
+
+use rand::Rng;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+/// Controls how many times, and how long apart, transient fetch failures are retried
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Calculates how long to wait after the given (1-based) failed attempt
+    ///
+    /// Uses "full jitter": a random delay between zero and the exponential backoff ceiling, so that
+    /// concurrent workers which failed at the same time don't all retry at the same time.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let ceiling = self.ceiling(attempt);
+
+        rand::thread_rng().gen_range(Duration::ZERO..=ceiling)
+    }
+
+    /// Upper bound of the backoff after the given (1-based) failed attempt
+    fn ceiling(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+
+        self.base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay)
+    }
+}
+
+/// Parses a `Retry-After` header, which is either a number of seconds or an HTTP date
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+
+    use super::{parse_retry_after, RetryPolicy};
+
+    #[test]
+    fn backoff_grows_exponentially_up_to_max_delay() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5),
+        };
+
+        assert_eq!(policy.ceiling(1), Duration::from_secs(1));
+        assert_eq!(policy.ceiling(2), Duration::from_secs(2));
+        assert_eq!(policy.ceiling(3), Duration::from_secs(4));
+        assert_eq!(policy.ceiling(4), Duration::from_secs(5));
+        assert_eq!(policy.ceiling(100), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn backoff_is_jittered_below_ceiling() {
+        let policy = RetryPolicy::default();
+
+        for attempt in 1..10 {
+            assert!(policy.backoff(attempt) <= policy.ceiling(attempt));
+        }
+    }
+
+    #[test]
+    fn parses_retry_after_seconds() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn parses_retry_after_http_date() {
+        let date = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(60));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_str(&date).unwrap());
+
+        let delay = parse_retry_after(&headers).unwrap();
+
+        assert!(delay > Duration::from_secs(55) && delay <= Duration::from_secs(60));
+    }
+
+    #[test]
+    fn ignores_missing_or_invalid_retry_after() {
+        let mut headers = HeaderMap::new();
+
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("soon"));
+
+        assert_eq!(parse_retry_after(&headers), None);
+    }
+}
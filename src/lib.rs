@@ -0,0 +1,30 @@
+//! Crawls [j-archive](https://j-archive.com/) and parses its pages into Jeopardy episode data
+//!
+//! The [`crawler`] fetches `showgame.php` pages and hands them to the [`parser`], which turns them
+//! into the [`models`] types. Saved pages can be parsed without any network access via [`offline`].
+//!
+//! ```
+//! use j_archive_crawler::{offline, serializer::SerializerBuilder};
+//!
+//! let path = std::path::Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/synthetic_game.html"));
+//! let episode = offline::parse_file(path).unwrap();
+//!
+//! let mut serializer = SerializerBuilder::new().build().unwrap();
+//! let json = format!("{0}{1}", serializer.serialize_episode(&episode), serializer.finish());
+//!
+//! assert!(json.contains("Plymouth Rock"));
+//! ```
+
+pub mod crawler;
+pub mod manifest;
+pub mod models;
+pub mod offline;
+pub mod parser;
+pub mod reporter;
+pub mod scores;
+pub mod serializer;
+mod utils;
+
+pub use crawler::{CrawlerError, JArchiveCrawler, JArchiveCrawlerBuilder};
+pub use models::{episode::JeopardyEpisode, question::JeopardyQuestion, round::JeopardyRound};
+pub use parser::{JArchiveDocumentParser, ParserError};
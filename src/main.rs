@@ -1,37 +1,338 @@
-mod crawler;
-mod models;
-mod parser;
-mod reporter;
-mod serializer;
-mod utils;
-
-use clap::Parser;
-use crawler::{CrawlerError, JArchiveCrawler};
-use models::cli_args::CliArgs;
-use reporter::ReporterBuilder;
+mod cli_args;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
+    path::Path,
+    time::Duration,
+};
+
+use chrono::NaiveDate;
+use clap::{CommandFactory, Parser};
+use cli_args::{CliArgs, Command};
+use j_archive_crawler::{
+    crawler::{HtmlCacheBuilder, JArchiveCrawler, JArchiveCrawlerBuilder, RetryPolicy},
+    manifest::{Manifest, ManifestRecord},
+    offline,
+    reporter::{Reporter, ReporterBuilder},
+    scores,
+    serializer::deserialize_episodes,
+    JeopardyEpisode,
+};
 
 #[tokio::main]
-async fn main() -> Result<(), CrawlerError> {
+async fn main() {
     let args = CliArgs::parse();
 
-    let results = JArchiveCrawler::new()
-        .crawl(args.episode_no, args.iterations.into())
-        .await;
+    if let (Some(from), Some(to)) = (args.from, args.to) {
+        if from > to {
+            CliArgs::command()
+                .error(
+                    clap::error::ErrorKind::ArgumentConflict,
+                    "--from must not be after --to",
+                )
+                .exit();
+        }
+    }
+
+    let mut reporter = ReporterBuilder::new()
+        .set_outfile(args.outfile.to_owned())
+        .set_format(args.format)
+        .build()
+        .expect("Could not build reporter");
+
+    match args.command {
+        Some(Command::Parse { ref paths }) => {
+            for (path, result) in offline::parse_paths(paths, args.text_format) {
+                match result {
+                    Ok(episode) => {
+                        write_episode(&mut reporter, episode, args.score_progression).await
+                    }
+                    Err(err) => eprintln!("Could not parse {0}: {1}", path.display(), err),
+                }
+            }
+        }
+        None => crawl(args, &mut reporter).await,
+    };
+
+    reporter
+        .finish()
+        .await
+        .expect("Unable to write results to outfile");
+}
+
+/// Crawls the episodes requested on the command line, writing each one as soon as it is parsed
+async fn crawl(args: CliArgs, reporter: &mut Reporter) {
+    let resuming = args.resume.is_some();
+    let score_progression = args.score_progression;
+    let crawler = build_crawler(&args);
+
+    let (episode_ids, seasons, mut manifest) = match (&args.resume, &args.manifest) {
+        (Some(resume), _) => {
+            let manifest = Manifest::open(resume).expect("Could not open manifest");
+            report_unreadable_lines(resume, &manifest);
+
+            (
+                manifest.pending(),
+                manifest.seasons().clone(),
+                Some(manifest),
+            )
+        }
+        (None, manifest) => {
+            let (episode_ids, seasons) = resolve_episodes(&args, &crawler).await;
+            let manifest = manifest.as_ref().map(|manifest| {
+                Manifest::create_with_seasons(manifest, &episode_ids, seasons.clone())
+                    .expect("Could not create manifest")
+            });
+
+            (episode_ids, seasons, manifest)
+        }
+    };
+
+    // the media downloads share the crawl's rate limit, which counts www.j-archive.com links
+    // against the same host as the pages
+    let media_crawler = args
+        .download_media
+        .as_ref()
+        .map(|dir| (crawler.clone(), dir));
+    let mut results = crawler.crawl_with_seasons(
+        episode_ids
+            .into_iter()
+            .map(|id| (id, seasons.get(&id).cloned()))
+            .collect(),
+    );
+
+    while let Some(result) = results.recv().await {
+        if let (Ok(episode), Some((crawler, dir))) = (&result, &media_crawler) {
+            download_media(crawler, episode, dir).await;
+        }
+
+        if let Some(manifest) = manifest.as_mut() {
+            manifest
+                .record(&ManifestRecord::from_result(&result))
+                .expect("Could not write to manifest");
+        }
+
+        match result {
+            // when resuming, the output is written from the manifest once the crawl finishes
+            Ok(_) if resuming => {}
+            Ok(episode) => write_episode(reporter, episode, score_progression).await,
+            Err(err) => eprintln!("{0}", err),
+        }
+    }
+
+    // leave the output unfinished rather than passing off a partial crawl as a complete one
+    if let Err(err) = results.finish().await {
+        eprintln!("The crawl was aborted: {0}", err);
+        std::process::exit(1);
+    }
 
-    match results {
-        Ok(episodes) => {
-            let reporter = ReporterBuilder::new()
-                .set_outfile(args.outfile)
-                .build()
-                .expect("Could not build reporter");
+    if let (true, Some(manifest)) = (resuming, manifest) {
+        for episode in manifest.episodes().expect("Could not read manifest") {
+            let episode = episode.expect("Could not read manifest");
 
-            reporter
-                .write(&episodes)
-                .await
-                .expect("Unable to write results to outfile");
+            write_episode(reporter, episode, score_progression).await;
         }
-        Err(err) => panic!("Encountered the following error: {0}", err),
+    }
+}
+
+/// Works out which episodes to crawl, along with the season of each when crawling by season or
+/// air date
+async fn resolve_episodes(
+    args: &CliArgs,
+    crawler: &JArchiveCrawler,
+) -> (Vec<u32>, BTreeMap<u32, String>) {
+    if args.latest {
+        let (season, game) = crawler
+            .latest_game()
+            .await
+            .expect("Could not find the latest episode")
+            .expect("j-archive does not list any episodes");
+
+        return (
+            vec![game.game_id],
+            BTreeMap::from([(game.game_id, season.id)]),
+        );
+    }
+
+    if let Some(player_id) = args.player {
+        let player = crawler
+            .player(player_id)
+            .await
+            .expect("Could not fetch the player");
+
+        if let Some(player_outfile) = &args.player_outfile {
+            let json = serde_json::to_string_pretty(&player).expect("Could not serialize player");
+            fs::write(player_outfile, json).expect("Unable to write player to outfile");
+        }
+
+        return (player.episode_ids(), BTreeMap::new());
+    }
+
+    let mut crawled = BTreeSet::new();
+    let mut air_dates = (args.from.is_some() || args.to.is_some()).then(|| {
+        (
+            args.from.unwrap_or(NaiveDate::MIN),
+            args.to.unwrap_or(NaiveDate::MAX),
+        )
+    });
+
+    if let Some(last_run) = &args.since_last_run {
+        let episodes = previous_episodes(last_run).expect("Could not read the last run");
+        let newest = episodes
+            .iter()
+            .filter_map(|episode| episode.air_date)
+            .max()
+            .expect("The last run does not have any episodes with an air date");
+
+        // games from the newest day are crawled again unless they were already there
+        crawled = episodes.iter().map(|episode| episode.id).collect();
+        air_dates = Some((newest, NaiveDate::MAX));
+    }
+
+    let seasons = if args.all_seasons {
+        let seasons = crawler.seasons().await.expect("Could not list seasons");
+
+        seasons.into_iter().map(|season| season.id).collect()
+    } else if let Some(season) = &args.season {
+        vec![season.to_owned()]
+    } else if let Some((from, to)) = air_dates {
+        let seasons = crawler
+            .seasons_aired_between(from, to)
+            .await
+            .expect("Could not list seasons");
+
+        seasons.into_iter().map(|season| season.id).collect()
+    } else {
+        let episode_ids =
+            (args.episode_no..(args.episode_no + u32::from(args.iterations))).collect();
+
+        return (episode_ids, BTreeMap::new());
     };
 
-    Ok(())
+    let mut episode_ids = Vec::new();
+    let mut episode_seasons = BTreeMap::new();
+
+    for season in seasons {
+        let games = match crawler.season_games(&season).await {
+            Ok(games) => games,
+            Err(err) => {
+                eprintln!("Could not list the games in season {0}: {1}", season, err);
+                continue;
+            }
+        };
+
+        for game in games {
+            if let Some((from, to)) = air_dates {
+                if !game.aired_between(from, to) {
+                    continue;
+                }
+            }
+
+            if crawled.contains(&game.game_id) {
+                continue;
+            }
+
+            if episode_seasons
+                .insert(game.game_id, season.to_owned())
+                .is_none()
+            {
+                episode_ids.push(game.game_id);
+            }
+        }
+    }
+
+    (episode_ids, episode_seasons)
+}
+
+/// Reads the episodes from an earlier run, which can be either its output or its manifest
+fn previous_episodes(path: &str) -> Result<Vec<JeopardyEpisode>, io::Error> {
+    if let Ok(episodes) = deserialize_episodes(&fs::read_to_string(path)?) {
+        return Ok(episodes);
+    }
+
+    let manifest = Manifest::open(path)?;
+    report_unreadable_lines(path, &manifest);
+
+    let episodes = manifest.episodes()?.collect();
+
+    episodes
+}
+
+/// Tells the user about manifest lines that had to be skipped, e.g. because a crash cut them off
+fn report_unreadable_lines(path: &str, manifest: &Manifest) {
+    for unreadable in manifest.unreadable_lines() {
+        eprintln!("{0}: {1}", path, unreadable);
+    }
+}
+
+/// Downloads the media every clue in an episode links to, reporting any that fail
+async fn download_media(crawler: &JArchiveCrawler, episode: &JeopardyEpisode, dir: &Path) {
+    let media = episode
+        .rounds
+        .iter()
+        .flat_map(|round| &round.questions)
+        .flat_map(|question| &question.media);
+
+    for media in media {
+        if let Err(err) = crawler.download_media(media, dir).await {
+            eprintln!(
+                "Episode {0}: could not download {1}: {2}",
+                episode.id, media.url, err
+            );
+        }
+    }
+}
+
+/// Builds the crawler from the command line arguments
+fn build_crawler(args: &CliArgs) -> JArchiveCrawler {
+    let cache = args.cache_dir.as_ref().map(|cache_dir| {
+        HtmlCacheBuilder::new()
+            .set_dir(cache_dir)
+            .set_compress(args.compress_cache)
+            .set_max_age(args.cache_max_age.map(Duration::from_secs))
+            .set_refresh(args.refresh)
+            .build()
+            .expect("Could not build cache")
+    });
+
+    JArchiveCrawlerBuilder::new()
+        .set_base_url(args.base_url.to_owned())
+        .set_report_progress(true)
+        .set_concurrency(args.concurrency.into())
+        .set_requests_per_second(args.requests_per_second)
+        .set_burst(args.burst)
+        .set_retry_policy(RetryPolicy {
+            max_retries: args.max_retries,
+            ..RetryPolicy::default()
+        })
+        .set_cache(cache)
+        .set_text_format(args.text_format)
+        .build()
+        .expect("Could not build crawler")
+}
+
+/// Writes an episode to the report, first rebuilding its score progression if asked to
+async fn write_episode(
+    reporter: &mut Reporter,
+    mut episode: JeopardyEpisode,
+    score_progression: bool,
+) {
+    if score_progression {
+        let progression = scores::reconstruct(&episode);
+
+        for mismatch in &progression.mismatches {
+            eprintln!(
+                "Episode {0}: {1}'s score at the end of the {2:?} round should be {3} but was rebuilt as {4}",
+                episode.id, mismatch.contestant, mismatch.round, mismatch.expected, mismatch.actual
+            );
+        }
+
+        episode.score_progression = Some(progression);
+    }
+
+    reporter
+        .write_episode(&episode)
+        .await
+        .expect("Unable to write results to outfile");
 }
@@ -0,0 +1,459 @@
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    crawler::{CrawlerError, CrawlerErrorCause},
+    models::episode::JeopardyEpisode,
+};
+
+/// A single line of the manifest file
+// records only live long enough to be written or read, so boxing the episode isn't worth it
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ManifestRecord<'a> {
+    /// The episodes the crawl was asked to fetch (always the first line)
+    Crawl {
+        episode_ids: Vec<u32>,
+        /// The episodes in each season, when crawling by season
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        seasons: BTreeMap<String, Vec<u32>>,
+    },
+
+    /// The episode was crawled and parsed
+    Succeeded {
+        id: u32,
+        episode: Cow<'a, JeopardyEpisode>,
+    },
+
+    /// The episode could not be crawled, but may succeed if retried
+    Failed { id: u32, reason: String },
+
+    /// The episode could not be crawled and retrying won't help, e.g. its page couldn't be parsed
+    Abandoned { id: u32, reason: String },
+
+    /// j-archive has no game with this id
+    Missing { id: u32 },
+}
+
+impl<'a> ManifestRecord<'a> {
+    /// Builds the record for the outcome of crawling an episode
+    pub fn from_result(result: &'a Result<JeopardyEpisode, CrawlerError>) -> Self {
+        match result {
+            Ok(episode) => ManifestRecord::Succeeded {
+                id: episode.id,
+                episode: Cow::Borrowed(episode),
+            },
+            Err(err) if matches!(err.cause, CrawlerErrorCause::MissingEpisode) => {
+                ManifestRecord::Missing { id: err.episode_no }
+            }
+            Err(err) if !err.cause.is_transient() => ManifestRecord::Abandoned {
+                id: err.episode_no,
+                reason: err.to_string(),
+            },
+            Err(err) => ManifestRecord::Failed {
+                id: err.episode_no,
+                reason: err.to_string(),
+            },
+        }
+    }
+
+    /// The episode this record describes, along with its outcome, given the record's position in the file
+    fn status(&self, offset: u64) -> Option<(u32, Status)> {
+        match self {
+            ManifestRecord::Crawl { .. } => None,
+            ManifestRecord::Succeeded { id, .. } => Some((*id, Status::Succeeded { offset })),
+            ManifestRecord::Failed { id, .. } => Some((*id, Status::Failed)),
+            ManifestRecord::Abandoned { id, .. } => Some((*id, Status::Abandoned)),
+            ManifestRecord::Missing { id } => Some((*id, Status::Missing)),
+        }
+    }
+}
+
+/// The outcome of an episode, as far as the manifest knows
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Status {
+    /// Succeeded, with the byte offset of the record holding the episode
+    Succeeded {
+        offset: u64,
+    },
+    Failed,
+    Abandoned,
+    Missing,
+}
+
+/// Append-only JSON Lines log of a crawl, which lets an interrupted crawl be resumed
+///
+/// Every episode is recorded as soon as it finishes, so a crash loses at most the episodes that
+/// were in flight. Later records for an episode replace earlier ones. Only the outcome of each
+/// episode is kept in memory; the episodes themselves are read back from disk when needed.
+pub struct Manifest {
+    path: PathBuf,
+    file: File,
+    len: u64,
+    episode_ids: Vec<u32>,
+    seasons: BTreeMap<u32, String>,
+    statuses: BTreeMap<u32, Status>,
+    unreadable_lines: Vec<UnreadableLine>,
+}
+
+/// A manifest line which couldn't be parsed, e.g. because a crash cut it off, and was skipped
+#[derive(thiserror::Error, Debug)]
+#[error("Skipped unreadable manifest line {line}: {source}")]
+pub struct UnreadableLine {
+    /// The line's number, counting from 1
+    pub line: usize,
+    pub source: serde_json::Error,
+}
+
+impl Manifest {
+    /// Starts a new manifest for a crawl of the given episodes, replacing any existing file
+    pub fn create(path: impl AsRef<Path>, episode_ids: &[u32]) -> Result<Self, io::Error> {
+        Manifest::create_with_seasons(path, episode_ids, BTreeMap::new())
+    }
+
+    /// Starts a new manifest for a crawl of the given episodes, remembering which season each
+    /// belongs to
+    pub fn create_with_seasons(
+        path: impl AsRef<Path>,
+        episode_ids: &[u32],
+        seasons: BTreeMap<u32, String>,
+    ) -> Result<Self, io::Error> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::create(&path)?;
+
+        let mut manifest = Manifest {
+            path,
+            file,
+            len: 0,
+            episode_ids: episode_ids.to_vec(),
+            seasons: seasons.clone(),
+            statuses: BTreeMap::new(),
+            unreadable_lines: Vec::new(),
+        };
+
+        let mut season_episodes: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        for (id, season) in seasons {
+            season_episodes.entry(season).or_default().push(id);
+        }
+
+        manifest.record(&ManifestRecord::Crawl {
+            episode_ids: episode_ids.to_vec(),
+            seasons: season_episodes,
+        })?;
+
+        Ok(manifest)
+    }
+
+    /// Opens an existing manifest so that its crawl can be resumed
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, io::Error> {
+        let path = path.as_ref().to_path_buf();
+
+        let mut episode_ids = None;
+        let mut seasons = BTreeMap::new();
+        let mut statuses = BTreeMap::new();
+        let mut unreadable_lines = Vec::new();
+
+        let mut reader = BufReader::new(File::open(&path)?);
+        let mut line = String::new();
+        let mut len = 0;
+        let mut ends_with_newline = true;
+
+        for line_number in 1.. {
+            line.clear();
+
+            let offset = len;
+            let read = reader.read_line(&mut line)?;
+
+            if read == 0 {
+                break;
+            }
+
+            len += read as u64;
+            ends_with_newline = line.ends_with('\n');
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let record: ManifestRecord = match serde_json::from_str(&line) {
+                Ok(record) => record,
+                Err(source) => {
+                    unreadable_lines.push(UnreadableLine {
+                        line: line_number,
+                        source,
+                    });
+
+                    continue;
+                }
+            };
+
+            if let Some((id, status)) = record.status(offset) {
+                statuses.insert(id, status);
+            } else if let ManifestRecord::Crawl {
+                episode_ids: ids,
+                seasons: crawl_seasons,
+            } = record
+            {
+                episode_ids = Some(ids);
+                seasons = crawl_seasons
+                    .into_iter()
+                    .flat_map(|(season, ids)| ids.into_iter().map(move |id| (id, season.clone())))
+                    .collect();
+            }
+        }
+
+        let Some(episode_ids) = episode_ids else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "manifest does not describe a crawl",
+            ));
+        };
+
+        let mut file = OpenOptions::new().append(true).open(&path)?;
+
+        // a crash can leave a partially written last line behind, so start on a fresh one
+        if !ends_with_newline {
+            file.write_all(b"\n")?;
+            len += 1;
+        }
+
+        Ok(Manifest {
+            path,
+            file,
+            len,
+            episode_ids,
+            seasons,
+            statuses,
+            unreadable_lines,
+        })
+    }
+
+    /// The lines which were skipped when the manifest was opened because they couldn't be parsed
+    pub fn unreadable_lines(&self) -> &[UnreadableLine] {
+        &self.unreadable_lines
+    }
+
+    /// The episodes which still need to be crawled (never attempted, or failed in a way that
+    /// retrying might fix)
+    pub fn pending(&self) -> Vec<u32> {
+        self.episode_ids
+            .iter()
+            .copied()
+            .filter(|id| {
+                !matches!(
+                    self.statuses.get(id),
+                    Some(Status::Succeeded { .. })
+                        | Some(Status::Abandoned)
+                        | Some(Status::Missing)
+                )
+            })
+            .collect()
+    }
+
+    /// The season each episode belongs to, if the crawl was by season
+    pub fn seasons(&self) -> &BTreeMap<u32, String> {
+        &self.seasons
+    }
+
+    /// Appends a record to the manifest, flushing it to disk immediately
+    pub fn record(&mut self, record: &ManifestRecord) -> Result<(), io::Error> {
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+
+        self.file.write_all(line.as_bytes())?;
+        self.file.flush()?;
+
+        if let Some((id, status)) = record.status(self.len) {
+            self.statuses.insert(id, status);
+        }
+
+        self.len += line.len() as u64;
+
+        Ok(())
+    }
+
+    /// Reads back every successfully crawled episode from disk, one at a time, ordered by episode number
+    pub fn episodes(
+        &self,
+    ) -> Result<impl Iterator<Item = Result<JeopardyEpisode, io::Error>> + '_, io::Error> {
+        let mut reader = BufReader::new(File::open(&self.path)?);
+        let mut line = String::new();
+
+        Ok(self.statuses.values().filter_map(move |status| {
+            let Status::Succeeded { offset } = status else {
+                return None;
+            };
+
+            line.clear();
+
+            let read = reader
+                .seek(SeekFrom::Start(*offset))
+                .and_then(|_| reader.read_line(&mut line));
+
+            Some(read.and_then(|_| match serde_json::from_str(&line)? {
+                ManifestRecord::Succeeded { episode, .. } => Ok(episode.into_owned()),
+                _ => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "manifest record moved while it was being read",
+                )),
+            }))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, collections::BTreeMap, fs::OpenOptions, io::Write};
+
+    use super::{Manifest, ManifestRecord};
+    use crate::{
+        crawler::{CrawlerError, CrawlerErrorCause},
+        models::episode::test_episode,
+        parser::ParserError,
+    };
+
+    #[test]
+    fn resumes_only_unfinished_episodes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifest.jsonl");
+
+        let mut manifest = Manifest::create(&path, &[1, 2, 3, 4]).unwrap();
+
+        manifest
+            .record(&ManifestRecord::Succeeded {
+                id: 1,
+                episode: Cow::Owned(test_episode(1)),
+            })
+            .unwrap();
+        manifest
+            .record(&ManifestRecord::Failed {
+                id: 2,
+                reason: "Received status 503".to_string(),
+            })
+            .unwrap();
+        manifest.record(&ManifestRecord::Missing { id: 3 }).unwrap();
+
+        let manifest = Manifest::open(&path).unwrap();
+
+        assert_eq!(manifest.pending(), vec![2, 4]);
+        assert_eq!(manifest.episodes().unwrap().count(), 1);
+    }
+
+    #[test]
+    fn does_not_resume_permanent_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifest.jsonl");
+
+        let mut manifest = Manifest::create(&path, &[1, 2]).unwrap();
+
+        let result = Err(CrawlerError {
+            episode_no: 1,
+            attempts: 1,
+            cause: CrawlerErrorCause::Parse(ParserError::NoRounds),
+        });
+        let record = ManifestRecord::from_result(&result);
+
+        assert!(matches!(record, ManifestRecord::Abandoned { id: 1, .. }));
+
+        manifest.record(&record).unwrap();
+
+        assert_eq!(Manifest::open(&path).unwrap().pending(), vec![2]);
+    }
+
+    #[test]
+    fn later_records_replace_earlier_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifest.jsonl");
+
+        let mut manifest = Manifest::create(&path, &[1]).unwrap();
+
+        for record in [
+            ManifestRecord::Failed {
+                id: 1,
+                reason: "timed out".to_string(),
+            },
+            ManifestRecord::Succeeded {
+                id: 1,
+                episode: Cow::Owned(test_episode(1)),
+            },
+            ManifestRecord::Succeeded {
+                id: 1,
+                episode: Cow::Owned(test_episode(1)),
+            },
+        ] {
+            manifest.record(&record).unwrap();
+        }
+
+        assert!(manifest.pending().is_empty());
+        assert_eq!(manifest.episodes().unwrap().count(), 1);
+    }
+
+    #[test]
+    fn recovers_from_partially_written_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifest.jsonl");
+
+        Manifest::create(&path, &[1, 2]).unwrap();
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"type":"succeeded","id":1,"epis"#)
+            .unwrap();
+
+        let mut manifest = Manifest::open(&path).unwrap();
+
+        assert_eq!(manifest.pending(), vec![1, 2]);
+        assert_eq!(manifest.unreadable_lines().len(), 1);
+        assert_eq!(manifest.unreadable_lines()[0].line, 2);
+
+        manifest.record(&ManifestRecord::Missing { id: 2 }).unwrap();
+
+        assert_eq!(Manifest::open(&path).unwrap().pending(), vec![1]);
+    }
+
+    #[test]
+    fn reads_episodes_recorded_with_display_air_dates() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifest.jsonl");
+
+        Manifest::create(&path, &[7515]).unwrap();
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(
+            file,
+            r#"{{"type":"succeeded","id":7515,"episode":{{"air_date":"Thursday, November 17, 2022","rounds":[{{"questions":[{{"prompt":"Tradition says the pilgrims set foot on this","category":"HISTORY","round":"Jeopardy","value":200,"answer":"Plymouth Rock"}}],"round":"Jeopardy"}}],"id":7515,"complete":true,"contestants":[],"final_scores":[]}}}}"#
+        )
+        .unwrap();
+
+        let manifest = Manifest::open(&path).unwrap();
+        let episodes = manifest
+            .episodes()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert!(manifest.pending().is_empty());
+        assert_eq!(
+            episodes[0].air_date,
+            chrono::NaiveDate::from_ymd_opt(2022, 11, 17)
+        );
+    }
+
+    #[test]
+    fn remembers_episode_seasons() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifest.jsonl");
+
+        let seasons = BTreeMap::from([(7515, "39".to_string()), (7516, "39".to_string())]);
+        Manifest::create_with_seasons(&path, &[7515, 7516], seasons.clone()).unwrap();
+
+        assert_eq!(Manifest::open(&path).unwrap().seasons(), &seasons);
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// A contestant as introduced at the top of the game page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Contestant {
+    pub name: String,
+    pub player_id: Option<u32>,
+    pub occupation: Option<String>,
+    pub hometown: Option<String>,
+    /// Whether the contestant came into the game as the returning champion
+    pub returning_champion: bool,
+    /// How many games the returning champion had won before this one
+    pub days_as_champion: Option<u32>,
+    /// The returning champion's cash winnings before this game
+    pub winnings: Option<u32>,
+}
@@ -1,19 +1,126 @@
-use serde::{Deserialize, Serialize};
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serialize};
 
-use super::{error::Error, round::JeopardyRound};
+use super::{
+    contestant::Contestant,
+    error::BuildError,
+    question::{JeopardyQuestion, Round},
+    round::JeopardyRound,
+    score::{Score, ScoreProgression},
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JeopardyEpisode {
-    pub air_date: Option<String>,
-    pub rounds: (JeopardyRound, JeopardyRound, JeopardyRound),
+    /// Written in ISO 8601 format, e.g. `2022-11-17`
+    #[serde(default, deserialize_with = "deserialize_air_date")]
+    pub air_date: Option<NaiveDate>,
+    /// The rounds found on the page, in the order they were played
+    pub rounds: Vec<JeopardyRound>,
     pub id: u32,
+    /// Whether the jeopardy, double jeopardy and final jeopardy rounds were all found
+    #[serde(default = "default_complete")]
+    pub complete: bool,
+    /// The number of the show as broadcast, which is not the same as j-archive's game id
+    #[serde(default)]
+    pub show_number: Option<u32>,
+    /// Notes j-archive adds to some games
+    #[serde(default)]
+    pub comments: Option<String>,
+    /// The tournament or special event named in the comments
+    #[serde(default)]
+    pub tournament: Option<String>,
+    /// The j-archive season the episode was crawled from, when crawling by season
+    #[serde(default)]
+    pub season: Option<String>,
+    #[serde(default)]
+    pub contestants: Vec<Contestant>,
+    #[serde(default)]
+    pub final_scores: Vec<Score>,
+    /// Only filled in on request, see [`crate::scores`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score_progression: Option<ScoreProgression>,
+}
+
+impl JeopardyEpisode {
+    /// Finds a round by its type
+    pub fn round(&self, round: Round) -> Option<&JeopardyRound> {
+        self.rounds.iter().find(|r| r.round == round)
+    }
+
+    /// The clues a player responded to, along with whether they got each one right
+    ///
+    /// Responses only name contestants by nickname, see [`JeopardyEpisode::nickname`].
+    pub fn responses_by(&self, player_id: u32) -> Vec<(&JeopardyQuestion, bool)> {
+        let Some(nickname) = self.nickname(player_id) else {
+            return Vec::new();
+        };
+
+        self.rounds
+            .iter()
+            .flat_map(|round| &round.questions)
+            .filter_map(|question| {
+                question
+                    .responses
+                    .iter()
+                    .find(|response| response.contestant == nickname)
+                    .map(|response| (question, response.correct))
+            })
+            .collect()
+    }
+
+    /// The nickname a player went by on their podium
+    ///
+    /// Score tables have a column per contestant, in the order the contestants were introduced, so
+    /// the nickname is read from the player's column. Games without score tables fall back to the
+    /// first word of the player's name.
+    pub fn nickname(&self, player_id: u32) -> Option<&str> {
+        let index = self
+            .contestants
+            .iter()
+            .position(|contestant| contestant.player_id == Some(player_id))?;
+
+        let score_table = std::iter::once(&self.final_scores)
+            .chain(self.rounds.iter().map(|round| &round.scores))
+            .find(|scores| scores.len() == self.contestants.len());
+
+        match score_table {
+            Some(scores) => Some(scores[index].contestant.as_str()),
+            None => self.contestants[index].name.split_whitespace().next(),
+        }
+    }
+}
+
+/// Reads an air date in ISO 8601 format, or as j-archive displays it (e.g. `Thursday, November 17,
+/// 2022`), which is how episodes recorded before air dates were parsed wrote it
+fn deserialize_air_date<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(air_date) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+
+    NaiveDate::parse_from_str(&air_date, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(air_date.trim(), "%A, %B %e, %Y"))
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+/// Episodes recorded before partial games were supported always had every round
+fn default_complete() -> bool {
+    true
 }
 
 #[derive(Default)]
 pub struct JeopardyEpisodeBuilder {
-    air_date: Option<String>,
-    rounds: Option<(JeopardyRound, JeopardyRound, JeopardyRound)>,
+    air_date: Option<NaiveDate>,
+    rounds: Option<Vec<JeopardyRound>>,
     id: Option<u32>,
+    contestants: Vec<Contestant>,
+    final_scores: Vec<Score>,
+    show_number: Option<u32>,
+    comments: Option<String>,
+    tournament: Option<String>,
 }
 
 impl JeopardyEpisodeBuilder {
@@ -23,17 +130,14 @@ impl JeopardyEpisodeBuilder {
     }
 
     // Sets the air date
-    pub fn set_air_date(&mut self, air_date: Option<String>) -> &mut Self {
+    pub fn set_air_date(&mut self, air_date: Option<NaiveDate>) -> &mut Self {
         self.air_date = air_date;
 
         self
     }
 
     // Sets the rounds
-    pub fn set_rounds(
-        &mut self,
-        rounds: (JeopardyRound, JeopardyRound, JeopardyRound),
-    ) -> &mut Self {
+    pub fn set_rounds(&mut self, rounds: Vec<JeopardyRound>) -> &mut Self {
         self.rounds = Some(rounds);
 
         self
@@ -46,20 +150,91 @@ impl JeopardyEpisodeBuilder {
         self
     }
 
+    // Sets the contestants
+    pub fn set_contestants(&mut self, contestants: Vec<Contestant>) -> &mut Self {
+        self.contestants = contestants;
+
+        self
+    }
+
+    // Sets the final scores
+    pub fn set_final_scores(&mut self, final_scores: Vec<Score>) -> &mut Self {
+        self.final_scores = final_scores;
+
+        self
+    }
+
+    // Sets the show number
+    pub fn set_show_number(&mut self, show_number: Option<u32>) -> &mut Self {
+        self.show_number = show_number;
+
+        self
+    }
+
+    // Sets the game comments
+    pub fn set_comments(&mut self, comments: Option<String>) -> &mut Self {
+        self.comments = comments;
+
+        self
+    }
+
+    // Sets the tournament
+    pub fn set_tournament(&mut self, tournament: Option<String>) -> &mut Self {
+        self.tournament = tournament;
+
+        self
+    }
+
     // Builds the episode
-    pub fn build(&self) -> Result<JeopardyEpisode, Error> {
+    pub fn build(&self) -> Result<JeopardyEpisode, BuildError> {
         let Some(id) = &self.id else {
-            return Err(Error::Static("Missing id"));
+            return Err(BuildError::Missing("id"));
         };
 
         let Some(rounds) = &self.rounds else {
-            return Err(Error::Static("Missing rounds"));
+            return Err(BuildError::Missing("rounds"));
         };
 
+        let complete = [Round::Jeopardy, Round::DoubleJeopardy, Round::FinalJeopardy]
+            .iter()
+            .all(|round| rounds.iter().any(|r| r.round == *round));
+
         Ok(JeopardyEpisode {
             id: id.to_owned(),
-            air_date: self.air_date.to_owned(),
+            complete,
+            air_date: self.air_date,
+            show_number: self.show_number,
+            comments: self.comments.to_owned(),
+            tournament: self.tournament.to_owned(),
+            season: None,
             rounds: rounds.to_owned(),
+            contestants: self.contestants.to_owned(),
+            final_scores: self.final_scores.to_owned(),
+            score_progression: None,
         })
     }
 }
+
+/// An episode with empty jeopardy, double jeopardy and final jeopardy rounds, for tests which
+/// don't care about its contents
+#[cfg(test)]
+pub(crate) fn test_episode(id: u32) -> JeopardyEpisode {
+    let round = |round| {
+        super::round::JeopardyRoundBuilder::new()
+            .set_questions(vec![])
+            .set_round(round)
+            .build()
+            .unwrap()
+    };
+
+    JeopardyEpisodeBuilder::new()
+        .set_id(id)
+        .set_air_date(NaiveDate::from_ymd_opt(2022, 11, 17))
+        .set_rounds(vec![
+            round(Round::Jeopardy),
+            round(Round::DoubleJeopardy),
+            round(Round::FinalJeopardy),
+        ])
+        .build()
+        .unwrap()
+}
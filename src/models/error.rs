@@ -1,6 +1,27 @@
+/// Error returned by the builders' `build` methods when they are given missing or invalid settings
 #[derive(thiserror::Error, Debug)]
-pub enum Error {
-    /// Static error (remove this in favor of a more specific struct once your class matures)
-    #[error("Static error: {0}")]
-    Static(&'static str),
+pub enum BuildError {
+    /// A required setting was never given
+    #[error("Missing {0}")]
+    Missing(&'static str),
+
+    /// The crawler was asked to crawl no episodes at a time
+    #[error("Concurrency must be greater than zero")]
+    ZeroConcurrency,
+
+    /// The crawler's request rate is zero, negative or not a number
+    #[error("Requests per second must be a positive number, got {0}")]
+    InvalidRequestsPerSecond(f64),
+
+    /// The crawler's base url is not a valid url
+    #[error("Invalid base url {url}: {source}")]
+    InvalidBaseUrl {
+        url: String,
+        #[source]
+        source: url::ParseError,
+    },
+
+    /// The crawler's http client could not be created
+    #[error("Could not build the http client: {0}")]
+    HttpClient(#[source] reqwest::Error),
 }
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Something about a clue that makes it hard to use without the show itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClueFlag {
+    /// The clue refers to a picture, audio or video that was shown alongside it
+    RequiresMedia,
+    /// The clue was presented by a member of the Clue Crew
+    HasClueCrew,
+    /// The clue plays a sound or piece of music
+    AudioClue,
+    /// The clue underlines letters for a wordplay category, which plain text loses
+    ContainsWordplayUnderline,
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// A picture, audio or video clip linked from a clue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MediaRef {
+    pub url: String,
+    #[serde(rename = "type")]
+    pub kind: MediaKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaKind {
+    Image,
+    Audio,
+    Video,
+    /// A link j-archive doesn't give a recognisable file extension
+    Other,
+}
+
+impl MediaRef {
+    /// Creates a reference to the media at a url, working out its kind from the file extension
+    pub fn new(url: String) -> Self {
+        let extension = url
+            .rsplit('/')
+            .next()
+            .and_then(|file_name| file_name.rsplit_once('.'))
+            .map(|(_, extension)| extension.to_ascii_lowercase());
+
+        let kind = match extension.as_deref() {
+            Some("jpg" | "jpeg" | "png" | "gif" | "bmp" | "webp") => MediaKind::Image,
+            Some("mp3" | "wav" | "m4a" | "ogg" | "aac") => MediaKind::Audio,
+            Some("mp4" | "mov" | "wmv" | "webm" | "m4v" | "avi") => MediaKind::Video,
+            _ => MediaKind::Other,
+        };
+
+        MediaRef { url, kind }
+    }
+
+    /// A file name for the media that stays the same between crawls
+    ///
+    /// The url's own file name is kept after a short hash of the whole url (e.g.
+    /// `1a2b3c4d_2022-11-17_J_11.jpg`), so that files with the same name at different urls don't
+    /// overwrite each other. Urls without a usable file name are named after their full hash.
+    pub fn file_name(&self) -> String {
+        let hash = format!("{:x}", Sha256::digest(self.url.as_bytes()));
+
+        let file_name = self
+            .url
+            .split(['?', '#'])
+            .next()
+            .and_then(|url| url.rsplit('/').next())
+            .unwrap_or_default();
+
+        let is_safe = !file_name.starts_with('.')
+            && file_name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+
+        if !file_name.is_empty() && is_safe {
+            return format!("{0}_{1}", &hash[..8], file_name);
+        }
+
+        hash
+    }
+}
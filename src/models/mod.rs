@@ -1,5 +1,12 @@
-pub mod cli_args;
+pub mod contestant;
 pub mod episode;
 pub mod error;
+pub mod flag;
+pub mod media;
+pub mod player;
 pub mod question;
+pub mod response;
 pub mod round;
+pub mod score;
+pub mod season;
+pub mod wager;
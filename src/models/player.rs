@@ -0,0 +1,34 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// A contestant's profile page, listing every game they played in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Player {
+    /// j-archive's id for the player, as linked from the contestants of an episode
+    pub id: u32,
+    pub name: String,
+    /// The introduction j-archive gives the player, e.g. "a software engineer from Austin, Texas"
+    pub bio: Option<String>,
+    /// The player's total cash winnings across every game
+    pub winnings: Option<u32>,
+    pub appearances: Vec<PlayerAppearance>,
+}
+
+impl Player {
+    /// The ids of the episodes the player appeared in, in the order they aired
+    pub fn episode_ids(&self) -> Vec<u32> {
+        self.appearances
+            .iter()
+            .map(|appearance| appearance.game_id)
+            .collect()
+    }
+}
+
+/// A game as listed on a player's page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerAppearance {
+    pub game_id: u32,
+    pub show_number: Option<u32>,
+    pub air_date: Option<NaiveDate>,
+    pub final_score: Option<i32>,
+}
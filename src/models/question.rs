@@ -2,13 +2,16 @@ use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
-use crate::utils::sanitizer::sanitize;
+use crate::utils::sanitizer::{sanitize, sanitize_as, TextFormat};
+
+use super::{flag::ClueFlag, media::MediaRef, response::ClueResponse, wager::FinalJeopardyWager};
 
 #[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum Round {
     Jeopardy,
     DoubleJeopardy,
     FinalJeopardy,
+    Tiebreaker,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,17 +21,67 @@ pub struct JeopardyQuestion {
     pub round: Round,
     pub value: Option<u32>,
     pub answer: Option<String>,
+    /// The board row (1 is the top, cheapest row), not set for final jeopardy
+    #[serde(default)]
+    pub row: Option<u32>,
+    /// The board column, i.e. which category the clue belongs to
+    #[serde(default)]
+    pub column: Option<u32>,
+    /// When the clue was picked, counting from 1 at the start of the round
+    #[serde(default)]
+    pub order: Option<u32>,
+    #[serde(default)]
+    pub daily_double: bool,
+    #[serde(default)]
+    pub wager: Option<u32>,
+    #[serde(default)]
+    pub final_wagers: Vec<FinalJeopardyWager>,
+    /// Every attempt at the clue, in the order they were made
+    #[serde(default)]
+    pub responses: Vec<ClueResponse>,
+    /// Whether nobody responded correctly
+    #[serde(default)]
+    pub triple_stumper: bool,
+    /// Pictures, audio and video the prompt links to
+    #[serde(default)]
+    pub media: Vec<MediaRef>,
+    /// Context the clue depends on which the text alone doesn't give
+    #[serde(default)]
+    pub flags: Vec<ClueFlag>,
 }
 
 impl JeopardyQuestion {
     /// Sanitizes the parsed j-archive strings
     pub fn sanitize(&self) -> JeopardyQuestion {
-        let sanitized_prompt = sanitize(&self.prompt);
+        self.sanitize_as(TextFormat::Plain)
+    }
+
+    /// Sanitizes the parsed j-archive strings, keeping the prompt and answer's formatting in the
+    /// given format
+    pub fn sanitize_as(&self, text_format: TextFormat) -> JeopardyQuestion {
+        let sanitized_prompt = sanitize_as(&self.prompt, text_format);
         let sanitized_cateogry = sanitize(&self.category);
-        let sanitized_answer = match &self.answer {
-            Some(value) => Some(sanitize(value)),
-            None => None,
-        };
+        let sanitized_answer = self
+            .answer
+            .as_deref()
+            .map(|answer| sanitize_as(answer, text_format));
+        let sanitized_final_wagers = self
+            .final_wagers
+            .iter()
+            .map(|final_wager| FinalJeopardyWager {
+                contestant: sanitize(&final_wager.contestant),
+                response: final_wager.response.as_deref().map(sanitize),
+                ..final_wager.to_owned()
+            })
+            .collect();
+        let sanitized_responses = self
+            .responses
+            .iter()
+            .map(|response| ClueResponse {
+                contestant: sanitize(&response.contestant),
+                correct: response.correct,
+            })
+            .collect();
 
         JeopardyQuestion {
             prompt: sanitized_prompt,
@@ -36,6 +89,16 @@ impl JeopardyQuestion {
             round: self.round,
             value: self.value,
             answer: sanitized_answer,
+            row: self.row,
+            column: self.column,
+            order: self.order,
+            daily_double: self.daily_double,
+            wager: self.wager,
+            final_wagers: sanitized_final_wagers,
+            responses: sanitized_responses,
+            triple_stumper: self.triple_stumper,
+            media: self.media.to_owned(),
+            flags: self.flags.to_owned(),
         }
     }
 }
@@ -47,6 +110,16 @@ pub struct JeopardyQuestionBuilder {
     round: Option<Round>,
     value: Option<u32>,
     answer: Option<String>,
+    row: Option<u32>,
+    column: Option<u32>,
+    order: Option<u32>,
+    daily_double: bool,
+    wager: Option<u32>,
+    final_wagers: Vec<FinalJeopardyWager>,
+    responses: Vec<ClueResponse>,
+    triple_stumper: bool,
+    media: Vec<MediaRef>,
+    flags: Vec<ClueFlag>,
 }
 
 /// Builder pattern for Jeopardy question object
@@ -91,6 +164,76 @@ impl JeopardyQuestionBuilder {
         self
     }
 
+    /// Sets the board row
+    pub fn set_row(&mut self, row: Option<u32>) -> &mut Self {
+        self.row = row;
+
+        self
+    }
+
+    /// Sets the board column
+    pub fn set_column(&mut self, column: Option<u32>) -> &mut Self {
+        self.column = column;
+
+        self
+    }
+
+    /// Sets the order the clue was picked in
+    pub fn set_order(&mut self, order: Option<u32>) -> &mut Self {
+        self.order = order;
+
+        self
+    }
+
+    /// Sets whether the question was a daily double
+    pub fn set_daily_double(&mut self, daily_double: bool) -> &mut Self {
+        self.daily_double = daily_double;
+
+        self
+    }
+
+    /// Sets the daily double wager
+    pub fn set_wager(&mut self, wager: Option<u32>) -> &mut Self {
+        self.wager = wager;
+
+        self
+    }
+
+    /// Sets the final jeopardy wagers
+    pub fn set_final_wagers(&mut self, final_wagers: Vec<FinalJeopardyWager>) -> &mut Self {
+        self.final_wagers = final_wagers;
+
+        self
+    }
+
+    /// Sets the responses
+    pub fn set_responses(&mut self, responses: Vec<ClueResponse>) -> &mut Self {
+        self.responses = responses;
+
+        self
+    }
+
+    /// Sets whether nobody responded correctly
+    pub fn set_triple_stumper(&mut self, triple_stumper: bool) -> &mut Self {
+        self.triple_stumper = triple_stumper;
+
+        self
+    }
+
+    /// Sets the media the prompt links to
+    pub fn set_media(&mut self, media: Vec<MediaRef>) -> &mut Self {
+        self.media = media;
+
+        self
+    }
+
+    /// Sets the flags the clue was classified with
+    pub fn set_flags(&mut self, flags: Vec<ClueFlag>) -> &mut Self {
+        self.flags = flags;
+
+        self
+    }
+
     /// Builds the object and returns it
     pub fn build(&self) -> Result<JeopardyQuestion, JeopardyQuestionBuilderError> {
         let Some(prompt) = &self.prompt else {
@@ -111,6 +254,16 @@ impl JeopardyQuestionBuilder {
             prompt: prompt.to_string(),
             round,
             value: self.value,
+            row: self.row,
+            column: self.column,
+            order: self.order,
+            daily_double: self.daily_double,
+            wager: self.wager,
+            final_wagers: self.final_wagers.to_owned(),
+            responses: self.responses.to_owned(),
+            triple_stumper: self.triple_stumper,
+            media: self.media.to_owned(),
+            flags: self.flags.to_owned(),
         })
     }
 }
@@ -124,15 +277,14 @@ impl fmt::Display for JeopardyQuestionBuilderError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{}",
-            format!(
-                "Could not build jeopardy question from data: err={0}",
-                self.msg
-            )
+            "Could not build jeopardy question from data: err={0}",
+            self.msg
         )
     }
 }
 
+impl std::error::Error for JeopardyQuestionBuilderError {}
+
 impl JeopardyQuestionBuilderError {
     pub fn new(msg: impl Into<String>) -> JeopardyQuestionBuilderError {
         JeopardyQuestionBuilderError { msg: msg.into() }
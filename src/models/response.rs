@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+/// A contestant's attempt at responding to a clue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClueResponse {
+    /// The nickname shown on the contestant's podium
+    pub contestant: String,
+    pub correct: bool,
+}
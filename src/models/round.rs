@@ -1,20 +1,25 @@
 use serde::{Deserialize, Serialize};
 
 use super::{
-    error::Error,
+    error::BuildError,
     question::{JeopardyQuestion, Round},
+    score::Score,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JeopardyRound {
     pub questions: Vec<JeopardyQuestion>,
     pub round: Round,
+    /// The scores j-archive printed at the end of the round
+    #[serde(default)]
+    pub scores: Vec<Score>,
 }
 
 #[derive(Default)]
 pub struct JeopardyRoundBuilder {
     questions: Option<Vec<JeopardyQuestion>>,
     round: Option<Round>,
+    scores: Vec<Score>,
 }
 
 impl JeopardyRoundBuilder {
@@ -37,19 +42,27 @@ impl JeopardyRoundBuilder {
         self
     }
 
+    /// Sets the scores at the end of the round
+    pub fn set_scores(&mut self, scores: Vec<Score>) -> &mut Self {
+        self.scores = scores;
+
+        self
+    }
+
     /// Builds the round
-    pub fn build(&mut self) -> Result<JeopardyRound, Error> {
+    pub fn build(&mut self) -> Result<JeopardyRound, BuildError> {
         let Some(questions) = &self.questions else {
-            return Err(Error::Static("Missing questions"));
+            return Err(BuildError::Missing("questions"));
         };
 
         let Some(round) = &self.round else {
-            return Err(Error::Static("Missing round"));
+            return Err(BuildError::Missing("round"));
         };
 
         Ok(JeopardyRound {
             questions: questions.to_owned(),
             round: round.to_owned(),
+            scores: self.scores.to_owned(),
         })
     }
 }
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use super::question::Round;
+
+/// A contestant's score as printed in one of j-archive's score tables
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Score {
+    /// The nickname shown on the contestant's podium
+    pub contestant: String,
+    pub score: i32,
+    /// The outcome j-archive records for the contestant at the end of the game, e.g.
+    /// `New champion: $18,900`
+    pub remarks: Option<String>,
+}
+
+/// The score of every contestant after each clue, rebuilt from the clues themselves
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreProgression {
+    /// The clues in the order they were played, final jeopardy last
+    pub clues: Vec<ScoredClue>,
+    /// One series per contestant, each with a score after every clue in `clues`
+    pub series: Vec<ScoreSeries>,
+    /// Places where the rebuilt scores disagree with j-archive's score tables
+    pub mismatches: Vec<ScoreMismatch>,
+}
+
+/// Identifies a clue within a score progression
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoredClue {
+    pub round: Round,
+    pub column: Option<u32>,
+    pub row: Option<u32>,
+}
+
+/// A single contestant's score after each clue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreSeries {
+    pub contestant: String,
+    pub scores: Vec<i32>,
+}
+
+/// A contestant whose rebuilt score at the end of a round differs from j-archive's
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreMismatch {
+    pub round: Round,
+    pub contestant: String,
+    /// The score j-archive printed
+    pub expected: i32,
+    /// The score rebuilt from the parsed clues
+    pub actual: i32,
+}
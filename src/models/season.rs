@@ -0,0 +1,42 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// A season as listed on j-archive's season index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Season {
+    /// j-archive's id for the season, usually its number (`39`) but sometimes a name
+    /// (`superjeopardy`)
+    pub id: String,
+    pub name: String,
+    pub first_aired: Option<NaiveDate>,
+    pub last_aired: Option<NaiveDate>,
+}
+
+impl Season {
+    /// Whether any of the season aired between two dates (inclusive)
+    ///
+    /// Seasons without a date range on the index might have aired at any time, so they are
+    /// assumed to overlap.
+    pub fn aired_between(&self, from: NaiveDate, to: NaiveDate) -> bool {
+        let starts_in_time = self.first_aired.is_none_or(|first_aired| first_aired <= to);
+        let ends_in_time = self.last_aired.is_none_or(|last_aired| last_aired >= from);
+
+        starts_in_time && ends_in_time
+    }
+}
+
+/// A game as listed on a season's page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeasonGame {
+    pub game_id: u32,
+    pub show_number: Option<u32>,
+    pub air_date: Option<NaiveDate>,
+}
+
+impl SeasonGame {
+    /// Whether the game aired between two dates (inclusive)
+    pub fn aired_between(&self, from: NaiveDate, to: NaiveDate) -> bool {
+        self.air_date
+            .is_some_and(|air_date| from <= air_date && air_date <= to)
+    }
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+/// A contestant's Final Jeopardy response and wager
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FinalJeopardyWager {
+    pub contestant: String,
+    pub response: Option<String>,
+    pub correct: bool,
+    pub wager: Option<u32>,
+}
@@ -0,0 +1,19 @@
+use std::io;
+
+use crate::parser::ParserError;
+
+/// Error raised when a saved page can't be turned into an episode
+#[derive(thiserror::Error, Debug)]
+pub enum OfflineError {
+    /// The page could not be read from disk
+    #[error("Could not read page: {0}")]
+    Io(#[from] io::Error),
+
+    /// Neither the file name nor the page say which game it is
+    #[error("Could not infer the game id from the file name or page")]
+    UnknownGameId,
+
+    /// The page was read but could not be parsed
+    #[error("Could not parse page: {0}")]
+    Parse(#[from] ParserError),
+}
@@ -0,0 +1,243 @@
+mod error;
+
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use flate2::read::GzDecoder;
+use regex::Regex;
+
+use crate::{
+    models::episode::JeopardyEpisode,
+    parser::{JArchiveDocumentParser, TextFormat},
+};
+
+pub use error::OfflineError;
+
+/// Matches the game id in a saved page's file name, e.g. `7515.html.gz` or `game_7515.html`
+static FILE_NAME_GAME_ID_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(?:.*game_id=|game[_-]?)?(\d+)(?:\.html?)?(?:\.gz)?$").unwrap()
+});
+
+/// Matches the game id in the links a showgame.php page has to the game's other pages
+static PAGE_GAME_ID_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"show(?:scores|gameresponses)\.php\?game_id=(\d+)").unwrap());
+
+/// Parses saved showgame.php pages from disk, without touching the network
+///
+/// Each path may be a page or a directory, which is searched recursively for `.html`, `.htm`
+/// and gzipped (`.gz`) pages. Pages are parsed one at a time as the iterator is advanced, ordered
+/// by the game id in their file name (pages without one come last). Every item pairs a path with
+/// its result, so paths which can't be read or parsed are left for the caller to report.
+pub fn parse_paths(
+    paths: &[PathBuf],
+    text_format: TextFormat,
+) -> impl Iterator<Item = (PathBuf, Result<JeopardyEpisode, OfflineError>)> {
+    let mut files = Vec::new();
+    let mut unreadable = Vec::new();
+
+    for path in paths {
+        if let Err(err) = collect_files(path, &mut files) {
+            unreadable.push((path.clone(), Err(OfflineError::Io(err))));
+        }
+    }
+
+    files.sort_by_key(|file| game_id_from_file_name(file).map_or((1, 0), |id| (0, id)));
+
+    unreadable
+        .into_iter()
+        .chain(files.into_iter().map(move |file| {
+            let result = parse_file_as(&file, text_format);
+            (file, result)
+        }))
+}
+
+/// Parses a single saved page
+pub fn parse_file(path: &Path) -> Result<JeopardyEpisode, OfflineError> {
+    parse_file_as(path, TextFormat::default())
+}
+
+/// Parses a single saved page, keeping the formatting of prompts and answers in the given format
+pub fn parse_file_as(
+    path: &Path,
+    text_format: TextFormat,
+) -> Result<JeopardyEpisode, OfflineError> {
+    let raw_html = read_page(path)?;
+
+    let Some(game_id) = infer_game_id(path, &raw_html) else {
+        return Err(OfflineError::UnknownGameId);
+    };
+
+    let document = scraper::Html::parse_document(&raw_html);
+
+    Ok(JArchiveDocumentParser::new(document, game_id)
+        .set_text_format(text_format)
+        .parse()?)
+}
+
+/// Works out which game a saved page belongs to, preferring the file name over the page itself
+///
+/// Understands wget style names (`showgame.php?game_id=7515`) and plain ids (`7515.html`,
+/// `game_7515.html`). Failing that, the page's own score and response links are used.
+pub fn infer_game_id(path: &Path, raw_html: &str) -> Option<u32> {
+    if let Some(id) = game_id_from_file_name(path) {
+        return Some(id);
+    }
+
+    PAGE_GAME_ID_PATTERN.captures(raw_html)?[1].parse().ok()
+}
+
+/// Reads the game id out of a saved page's file name, if it has one
+fn game_id_from_file_name(path: &Path) -> Option<u32> {
+    let file_name = path.file_name()?.to_string_lossy();
+
+    // a number too large to be a game id isn't one, so the caller falls back to the page's links
+    FILE_NAME_GAME_ID_PATTERN.captures(&file_name)?[1]
+        .parse()
+        .ok()
+}
+
+/// Reads a page from disk, decompressing it if it is gzipped
+fn read_page(path: &Path) -> Result<String, io::Error> {
+    let bytes = fs::read(path)?;
+
+    if path.extension().is_some_and(|extension| extension == "gz") {
+        let mut raw_html = String::new();
+        GzDecoder::new(bytes.as_slice()).read_to_string(&mut raw_html)?;
+
+        Ok(raw_html)
+    } else {
+        String::from_utf8(bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+/// Adds the path, or every page below it if it is a directory, to `files`
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<(), io::Error> {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+
+        return Ok(());
+    }
+
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    entries.sort();
+
+    for entry in entries {
+        if entry.is_dir() {
+            collect_files(&entry, files)?;
+        } else if is_page(&entry) {
+            files.push(entry);
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether a file found while walking a directory looks like a saved page
+fn is_page(path: &Path) -> bool {
+    let file_name = path
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    file_name.ends_with(".html")
+        || file_name.ends_with(".htm")
+        || file_name.ends_with(".gz")
+        || file_name.contains("showgame.php")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        io::Write,
+        path::{Path, PathBuf},
+    };
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::{infer_game_id, parse_paths, OfflineError, TextFormat};
+
+    const FIXTURE: &str = include_str!("../../fixtures/synthetic_game.html");
+
+    #[test]
+    fn infers_game_id_from_file_name() {
+        for file_name in [
+            "showgame.php?game_id=7515",
+            "7515.html",
+            "7515.html.gz",
+            "game_7515.html",
+            "Game-7515.htm",
+        ] {
+            assert_eq!(infer_game_id(Path::new(file_name), ""), Some(7515));
+        }
+    }
+
+    #[test]
+    fn infers_game_id_from_page() {
+        let path = Path::new("5d41402abc4b2a76b9719d911017c592.html");
+
+        assert_eq!(infer_game_id(path, FIXTURE), Some(7515));
+        assert_eq!(infer_game_id(path, "<html></html>"), None);
+    }
+
+    #[test]
+    fn falls_back_to_page_when_file_name_number_is_too_large() {
+        let path = Path::new("20221117093000.html");
+
+        assert_eq!(infer_game_id(path, FIXTURE), Some(7515));
+        assert_eq!(infer_game_id(path, "<html></html>"), None);
+    }
+
+    #[test]
+    fn parses_saved_pages_in_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("mirror");
+        fs::create_dir(&nested).unwrap();
+
+        fs::write(nested.join("showgame.php?game_id=7515"), FIXTURE).unwrap();
+        fs::write(dir.path().join("notes.txt"), "not a page").unwrap();
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(FIXTURE.as_bytes()).unwrap();
+        fs::write(dir.path().join("7516.html.gz"), encoder.finish().unwrap()).unwrap();
+
+        // ordered by game id, not by where the pages were found
+        let episodes = parse_paths(&[dir.path().to_path_buf()], TextFormat::Plain);
+
+        assert_eq!(
+            episodes
+                .map(|(_, episode)| episode.unwrap().id)
+                .collect::<Vec<_>>(),
+            vec![7515, 7516]
+        );
+    }
+
+    #[test]
+    fn reports_unparseable_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let page = dir.path().join("7515.html");
+        let missing = PathBuf::from("missing.html");
+
+        fs::write(&page, "<p>ERROR: No game 7515 in database.</p>").unwrap();
+
+        let results =
+            parse_paths(&[page.clone(), missing.clone()], TextFormat::Plain).collect::<Vec<_>>();
+
+        assert_eq!(results.len(), 2);
+        assert!(matches!(
+            &results[0],
+            (path, Err(OfflineError::Parse(_))) if *path == page
+        ));
+        assert!(matches!(
+            &results[1],
+            (path, Err(OfflineError::Io(_))) if *path == missing
+        ));
+    }
+}
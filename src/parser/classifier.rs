@@ -0,0 +1,231 @@
+use regex::{Regex, RegexBuilder};
+
+use crate::models::{
+    flag::ClueFlag,
+    media::{MediaKind, MediaRef},
+};
+use crate::utils::sanitizer::sanitize;
+
+/// When a clue should be given a flag
+///
+/// A clue gets the flag if any one of the rule's signs is present. Patterns ignore case.
+struct Rule {
+    flag: ClueFlag,
+    /// Matched against the prompt's text
+    text_patterns: &'static [&'static str],
+    /// Matched against the prompt's html, for signs that only survive as markup
+    html_patterns: &'static [&'static str],
+    /// Kinds of linked media that imply the flag
+    media_kinds: &'static [MediaKind],
+}
+
+const RULES: [Rule; 4] = [
+    Rule {
+        flag: ClueFlag::RequiresMedia,
+        text_patterns: &[
+            r"\b(seen|shown|pictured|heard|featured|displayed) here\b",
+            r"\b(seen|shown) on the (monitor|screen|board)\b",
+            r"\bin (the|this) (video|clip|photo|picture)\b",
+        ],
+        html_patterns: &[],
+        media_kinds: &[
+            MediaKind::Image,
+            MediaKind::Audio,
+            MediaKind::Video,
+            MediaKind::Other,
+        ],
+    },
+    Rule {
+        flag: ClueFlag::HasClueCrew,
+        text_patterns: &[r"\bclue crew\b"],
+        html_patterns: &[],
+        media_kinds: &[],
+    },
+    Rule {
+        flag: ClueFlag::AudioClue,
+        text_patterns: &[
+            r"\bheard here\b",
+            r"\byou('ll)? hear\b",
+            r"\b(this|the) (audio|recording|song clip)\b",
+        ],
+        html_patterns: &[],
+        media_kinds: &[MediaKind::Audio],
+    },
+    Rule {
+        flag: ClueFlag::ContainsWordplayUnderline,
+        text_patterns: &[],
+        html_patterns: &[r"<u[\s>]"],
+        media_kinds: &[],
+    },
+];
+
+/// A rule with its patterns compiled
+struct CompiledRule {
+    flag: ClueFlag,
+    text_patterns: Vec<Regex>,
+    html_patterns: Vec<Regex>,
+    media_kinds: &'static [MediaKind],
+}
+
+/// Flags clues which depend on context the text alone doesn't give, like pictures or underlined
+/// letters, so they can be filtered out
+pub struct ClueClassifier {
+    rules: Vec<CompiledRule>,
+}
+
+impl ClueClassifier {
+    /// Compiles the classification rules
+    pub fn new() -> Self {
+        let compile = |patterns: &[&str]| {
+            patterns
+                .iter()
+                .map(|pattern| {
+                    RegexBuilder::new(pattern)
+                        .case_insensitive(true)
+                        .build()
+                        .expect("Invalid clue classification pattern")
+                })
+                .collect()
+        };
+
+        ClueClassifier {
+            rules: RULES
+                .iter()
+                .map(|rule| CompiledRule {
+                    flag: rule.flag,
+                    text_patterns: compile(rule.text_patterns),
+                    html_patterns: compile(rule.html_patterns),
+                    media_kinds: rule.media_kinds,
+                })
+                .collect(),
+        }
+    }
+
+    /// Works out which flags a clue should have, given its prompt's html and the media it links to
+    pub fn classify(&self, prompt_html: &str, media: &[MediaRef]) -> Vec<ClueFlag> {
+        let text = sanitize(prompt_html);
+
+        self.rules
+            .iter()
+            .filter(|rule| {
+                rule.text_patterns
+                    .iter()
+                    .any(|pattern| pattern.is_match(&text))
+                    || rule
+                        .html_patterns
+                        .iter()
+                        .any(|pattern| pattern.is_match(prompt_html))
+                    || media
+                        .iter()
+                        .any(|media| rule.media_kinds.contains(&media.kind))
+            })
+            .map(|rule| rule.flag)
+            .collect()
+    }
+}
+
+impl Default for ClueClassifier {
+    fn default() -> Self {
+        ClueClassifier::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ClueClassifier;
+    use crate::models::{flag::ClueFlag, media::MediaRef};
+
+    fn classify(prompt_html: &str, media_urls: &[&str]) -> Vec<ClueFlag> {
+        let media = media_urls
+            .iter()
+            .map(|url| MediaRef::new(url.to_string()))
+            .collect::<Vec<_>>();
+
+        ClueClassifier::new().classify(prompt_html, &media)
+    }
+
+    #[test]
+    fn leaves_self_contained_clues_alone() {
+        assert!(classify(
+            "Tradition says the pilgrims set foot on this historic artifact on December 26, 1620",
+            &[]
+        )
+        .is_empty());
+        assert!(classify("<i>The Raven</i> was published in this year", &[]).is_empty());
+    }
+
+    #[test]
+    fn flags_clues_with_pictures() {
+        assert_eq!(
+            classify(
+                r#"Seen <a href="https://www.j-archive.com/media/2022-11-17_J_11.jpg" target="_blank">here</a>, this capital's statehouse dome is covered in gold leaf"#,
+                &["https://www.j-archive.com/media/2022-11-17_J_11.jpg"]
+            ),
+            vec![ClueFlag::RequiresMedia]
+        );
+    }
+
+    #[test]
+    fn flags_clues_referring_to_missing_media() {
+        // j-archive doesn't always have the media a clue was shown with
+        assert_eq!(
+            classify(
+                "Pictured here, this bird is the state bird of 7 states",
+                &[]
+            ),
+            vec![ClueFlag::RequiresMedia]
+        );
+        assert_eq!(
+            classify("The man seen on the monitor wrote this novel", &[]),
+            vec![ClueFlag::RequiresMedia]
+        );
+    }
+
+    #[test]
+    fn flags_clue_crew_clues() {
+        assert_eq!(
+            classify(
+                r#"(<a href="https://www.j-archive.com/media/2022-11-17_J_24.mp4" target="_blank">Sarah of the Clue Crew presents from a kitchen.</a>) This French term means to cut vegetables into thin matchsticks"#,
+                &["https://www.j-archive.com/media/2022-11-17_J_24.mp4"]
+            ),
+            vec![ClueFlag::RequiresMedia, ClueFlag::HasClueCrew]
+        );
+        assert_eq!(
+            classify(
+                "(Jimmy of the Clue Crew reports from Greenwich.) The prime meridian runs through this borough",
+                &[]
+            ),
+            vec![ClueFlag::HasClueCrew]
+        );
+    }
+
+    #[test]
+    fn flags_audio_clues() {
+        assert_eq!(
+            classify(
+                "Heard here, this composer's 5th symphony opens with 4 famous notes",
+                &[]
+            ),
+            vec![ClueFlag::RequiresMedia, ClueFlag::AudioClue]
+        );
+        assert_eq!(
+            classify(
+                r#"<a href="https://www.j-archive.com/media/2004-06-02_DJ_3.mp3">This</a> bird's call"#,
+                &["https://www.j-archive.com/media/2004-06-02_DJ_3.mp3"]
+            ),
+            vec![ClueFlag::RequiresMedia, ClueFlag::AudioClue]
+        );
+    }
+
+    #[test]
+    fn flags_underlined_wordplay() {
+        assert_eq!(
+            classify(
+                "<u>C</u>AT + <u>H</u>AT: the underlined letters begin this",
+                &[]
+            ),
+            vec![ClueFlag::ContainsWordplayUnderline]
+        );
+        assert!(classify("Underlined in red, this", &[]).is_empty());
+    }
+}
@@ -0,0 +1,28 @@
+use crate::models::{error::BuildError, question::JeopardyQuestionBuilderError, question::Round};
+
+/// Error raised when a j-archive page can't be parsed into an episode
+#[derive(thiserror::Error, Debug)]
+pub enum ParserError {
+    /// The page has no rounds at all
+    #[error("Could not locate any rounds")]
+    NoRounds,
+
+    /// A clue sits in a column which has no category
+    #[error("Could not locate the category for column {column:?} of the {round:?} round")]
+    MissingCategory { round: Round, column: Option<u32> },
+
+    /// The episode's data could not be put together
+    #[error("Could not build the episode: {0}")]
+    InvalidEpisode(#[source] BuildError),
+
+    /// A round's data could not be put together
+    #[error("Could not build the {round:?} round: {source}")]
+    InvalidRound { round: Round, source: BuildError },
+
+    /// A clue's data could not be put together
+    #[error("Could not build a clue in the {round:?} round: {source}")]
+    InvalidQuestion {
+        round: Round,
+        source: JeopardyQuestionBuilderError,
+    },
+}
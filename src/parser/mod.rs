@@ -1,17 +1,99 @@
+mod classifier;
+mod error;
+mod player;
+mod season;
+
+use std::sync::LazyLock;
+
+use chrono::{Datelike, NaiveDate};
+use regex::Regex;
 use scraper::{ElementRef, Html, Selector};
 
 use crate::models::{
+    contestant::Contestant,
     episode::{JeopardyEpisode, JeopardyEpisodeBuilder},
-    error::Error,
+    media::{MediaKind, MediaRef},
     question::{JeopardyQuestion, JeopardyQuestionBuilder, Round},
+    response::ClueResponse,
     round::{JeopardyRound, JeopardyRoundBuilder},
+    score::Score,
+    wager::FinalJeopardyWager,
 };
+use crate::utils::{currency::parse_amount, sanitizer::sanitize};
+
+pub use classifier::ClueClassifier;
+pub use error::ParserError;
+pub use player::parse_player;
+pub use season::{parse_season_games, parse_seasons};
+
+pub use crate::utils::sanitizer::TextFormat;
+
+/// The top row's value in the jeopardy round, and the air date (year, month, day) it took effect
+///
+/// Double jeopardy values are always twice the jeopardy round's
+const BASE_VALUES: [((i32, u32, u32), u32); 2] = [((1984, 9, 10), 100), ((2001, 11, 26), 200)];
 
-const NUM_CATEGORIES: usize = 6;
+/// Special events named in game comments, checked longest first so that e.g. the "Ultimate
+/// Tournament of Champions" isn't labelled as a regular "Tournament of Champions"
+const TOURNAMENTS: [&str; 18] = [
+    "Ultimate Tournament of Champions",
+    "Tournament of Champions",
+    "National College Championship",
+    "College Championship",
+    "Teen Tournament",
+    "Teachers Tournament",
+    "Professors Tournament",
+    "Kids Week",
+    "Back to School Week",
+    "Celebrity Jeopardy!",
+    "Power Players Week",
+    "Battle of the Decades",
+    "Million Dollar Masters",
+    "Jeopardy! Masters",
+    "All-Star Games",
+    "Champions Wildcard",
+    "Second Chance",
+    "Super Jeopardy!",
+];
 
+/// Special events whose boards don't reliably use the regular values, so clues which don't display
+/// a value are left without one rather than given a guess
+const IRREGULAR_BOARD_TOURNAMENTS: [&str; 5] = [
+    "Celebrity Jeopardy!",
+    "Kids Week",
+    "Back to School Week",
+    "Power Players Week",
+    "Super Jeopardy!",
+];
+
+/// Where relative media links in prompts point unless the parser is given another base url
+const DEFAULT_MEDIA_BASE_URL: &str = "https://www.j-archive.com/";
+
+/// Compiling the classification rules is costly, so every parser shares one classifier
+static CLASSIFIER: LazyLock<ClueClassifier> = LazyLock::new(ClueClassifier::new);
+
+/// Matches the note j-archive adds to a returning champion's description
+static CHAMPION_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\(whose (\d+)-day cash winnings total (\$[\d,]+)\)").unwrap());
+
+/// Parses a j-archive `showgame.php` page into a [`JeopardyEpisode`]
+///
+/// ```
+/// use j_archive_crawler::parser::JArchiveDocumentParser;
+///
+/// let html = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/synthetic_game.html"));
+/// let document = scraper::Html::parse_document(html);
+///
+/// let episode = JArchiveDocumentParser::new(document, 7515).parse().unwrap();
+///
+/// assert_eq!(episode.id, 7515);
+/// assert_eq!(episode.rounds[0].questions[0].answer.as_deref(), Some("Plymouth Rock"));
+/// ```
 pub struct JArchiveDocumentParser {
     document: Html,
     episode_no: u32,
+    text_format: TextFormat,
+    base_url: reqwest::Url,
 }
 
 impl JArchiveDocumentParser {
@@ -20,75 +102,268 @@ impl JArchiveDocumentParser {
         JArchiveDocumentParser {
             document,
             episode_no,
+            text_format: TextFormat::default(),
+            base_url: reqwest::Url::parse(DEFAULT_MEDIA_BASE_URL).unwrap(),
         }
     }
 
+    /// Sets how much of the formatting in prompts and answers is kept (plain text by default)
+    pub fn set_text_format(&mut self, text_format: TextFormat) -> &mut Self {
+        self.text_format = text_format;
+
+        self
+    }
+
+    /// Sets the url relative media links are resolved against (j-archive by default)
+    pub fn set_base_url(&mut self, base_url: reqwest::Url) -> &mut Self {
+        self.base_url = base_url;
+
+        self
+    }
+
     /// Parses the provided document into jeopardy episode data
-    pub fn parse(&self) -> Result<JeopardyEpisode, Error> {
-        let Ok(rounds) = self.parse_rounds() else {
-            return Err(Error::Static("Failed to parse episode data"));
-        };
+    pub fn parse(&self) -> Result<JeopardyEpisode, ParserError> {
+        let rounds = self.parse_rounds()?;
+        let comments = self.parse_comments();
 
-        Ok(JeopardyEpisodeBuilder::new()
+        JeopardyEpisodeBuilder::new()
             .set_id(self.episode_no)
             .set_rounds(rounds)
             .set_air_date(self.parse_air_date())
+            .set_show_number(self.parse_show_number())
+            .set_comments(comments.clone())
+            .set_tournament(comments.as_deref().and_then(tournament_label))
+            .set_contestants(self.parse_contestants())
+            .set_final_scores(self.parse_final_scores())
             .build()
-            .expect("Could not build jeopardy episode from the given data"))
+            .map_err(ParserError::InvalidEpisode)
+    }
+
+    /// Parses the game title, e.g. `Show #8745 - Thursday, November 17, 2022`
+    fn parse_title(&self) -> Option<String> {
+        let title_selector = Selector::parse("#game_title h1").unwrap();
+
+        let title_element = self.document.select(&title_selector).next()?;
+
+        Some(title_element.text().collect())
     }
 
     /// Parses the air date
-    fn parse_air_date(&self) -> Option<String> {
-        let air_date_selector = Selector::parse("#game_title h1").unwrap();
+    fn parse_air_date(&self) -> Option<NaiveDate> {
+        let title = self.parse_title()?;
 
-        let Some(air_date_element) = self.document.select(&air_date_selector).next() else {
-            return None;
-        };
+        let date = title.split(" - ").nth(1)?;
 
-        let date = air_date_element.inner_html();
+        NaiveDate::parse_from_str(date.trim(), "%A, %B %e, %Y").ok()
+    }
 
-        let Some(date) = date.split(" - ").nth(1) else {
-            return None;
+    /// Parses the show number, which is not the same as j-archive's game id
+    fn parse_show_number(&self) -> Option<u32> {
+        let title = self.parse_title()?;
+
+        let show = title.split(" - ").next()?;
+
+        show.trim().strip_prefix("Show #")?.parse().ok()
+    }
+
+    /// Parses the comments j-archive adds to some games, e.g. which tournament they were part of
+    fn parse_comments(&self) -> Option<String> {
+        let comments_selector = Selector::parse("#game_comments").unwrap();
+
+        let comments = self.document.select(&comments_selector).next()?;
+        let comments = sanitize(&comments.inner_html());
+
+        (!comments.is_empty()).then_some(comments)
+    }
+
+    /// Parses the contestants introduced at the top of the page
+    fn parse_contestants(&self) -> Vec<Contestant> {
+        let contestant_selector = Selector::parse("p.contestants").unwrap();
+        let link_selector = Selector::parse("a").unwrap();
+
+        self.document
+            .select(&contestant_selector)
+            .filter_map(|contestant| {
+                let link = contestant.select(&link_selector).next()?;
+                let name = sanitize(&link.inner_html());
+
+                let player_id = link
+                    .value()
+                    .attr("href")
+                    .and_then(|href| href.split("player_id=").nth(1))
+                    .and_then(|player_id| player_id.parse().ok());
+
+                // everything after the name, e.g. ", a librarian from Portland, Maine"
+                let description = contestant.text().collect::<String>();
+                let description = description
+                    .split_once(&link.text().collect::<String>())
+                    .map_or("", |(_, description)| description)
+                    .trim_start_matches(',');
+
+                Some(self.parse_contestant_description(name, player_id, description))
+            })
+            .collect()
+    }
+
+    /// Parses a contestant's occupation, hometown and champion status from their introduction
+    fn parse_contestant_description(
+        &self,
+        name: String,
+        player_id: Option<u32>,
+        description: &str,
+    ) -> Contestant {
+        let champion = CHAMPION_PATTERN.captures(description);
+        let description = CHAMPION_PATTERN.replace(description, "");
+        let description = sanitize(&description);
+
+        let (occupation, hometown) = match description.rsplit_once(" from ") {
+            Some((occupation, hometown)) => (occupation, Some(hometown.to_string())),
+            None => (description.as_str(), None),
         };
 
-        Some(date.to_string())
+        let occupation = occupation
+            .strip_prefix("an ")
+            .or_else(|| occupation.strip_prefix("a "))
+            .unwrap_or(occupation)
+            .trim();
+
+        Contestant {
+            name,
+            player_id,
+            occupation: (!occupation.is_empty()).then(|| occupation.to_string()),
+            hometown,
+            returning_champion: champion.is_some(),
+            days_as_champion: champion
+                .as_ref()
+                .and_then(|champion| champion[1].parse().ok()),
+            winnings: champion
+                .as_ref()
+                .and_then(|champion| parse_amount(&champion[2]))
+                .and_then(|amount| u32::try_from(amount).ok()),
+        }
     }
 
-    /// Parses all rounds
-    fn parse_rounds(&self) -> Result<(JeopardyRound, JeopardyRound, JeopardyRound), Error> {
-        let mut round_builder = JeopardyRoundBuilder::new();
+    /// Parses the scores at the end of final jeopardy
+    fn parse_final_scores(&self) -> Vec<Score> {
+        self.parse_scores_after("Final scores")
+    }
+
+    /// Parses the scores at the end of a round
+    fn parse_round_scores(&self, round: Round) -> Vec<Score> {
+        match round {
+            Round::Jeopardy => self.parse_scores_after("Scores at the end of the Jeopardy!"),
+            Round::DoubleJeopardy => {
+                self.parse_scores_after("Scores at the end of the Double Jeopardy!")
+            }
+            Round::FinalJeopardy => self.parse_final_scores(),
+            Round::Tiebreaker => Vec::new(),
+        }
+    }
+
+    /// Parses the score table following the heading which starts with `heading_prefix`
+    fn parse_scores_after(&self, heading_prefix: &str) -> Vec<Score> {
+        let heading_selector = Selector::parse("h3").unwrap();
 
-        let Ok(jeopardy_questions) = self.parse_questions(Round::Jeopardy) else {
-            return Err(Error::Static("Could not parse jeopardy questions"));
+        let Some(heading) = self.document.select(&heading_selector).find(|heading| {
+            heading
+                .text()
+                .collect::<String>()
+                .starts_with(heading_prefix)
+        }) else {
+            return Vec::new();
         };
 
-        let Ok(double_jeopardy_questions) = self.parse_questions(Round::DoubleJeopardy) else {
-            return Err(Error::Static("Could not parse double jeopardy questions"));
+        let Some(table) = heading
+            .next_siblings()
+            .filter_map(ElementRef::wrap)
+            .find(|sibling| sibling.value().name() == "table")
+        else {
+            return Vec::new();
         };
 
-        let Ok(final_jeopardy_question) = self.parse_questions(Round::FinalJeopardy) else {
-            return Err(Error::Static("Could not parse final jeopardy question"));
+        self.parse_score_table(table)
+    }
+
+    /// Parses a score table, which has a column per contestant with their nickname, score and
+    /// (at the end of the game) remarks
+    ///
+    /// Cells are matched up by column, so a contestant with a missing score is skipped without
+    /// shifting anyone else's remarks onto them
+    fn parse_score_table(&self, table: ElementRef) -> Vec<Score> {
+        let row_selector = Selector::parse("tr").unwrap();
+        let cell_selector = Selector::parse("td").unwrap();
+
+        let rows: Vec<Vec<ElementRef>> = table
+            .select(&row_selector)
+            .map(|row| row.select(&cell_selector).collect())
+            .collect();
+
+        // the row whose cells are classed with any of the given classes
+        let find_row = |classes: &[&str]| {
+            rows.iter().find(|cells| {
+                cells
+                    .iter()
+                    .any(|cell| cell.value().classes().any(|class| classes.contains(&class)))
+            })
         };
 
-        let jeopardy_round = round_builder
-            .set_questions(jeopardy_questions)
-            .set_round(Round::Jeopardy)
-            .build()
-            .expect("Could not build jeopardy round from the provided data");
+        let Some(nicknames) = find_row(&["score_player_nickname"]) else {
+            return Vec::new();
+        };
+        let scores = find_row(&["score_positive", "score_negative"]);
+        let remarks = find_row(&["score_remarks"]);
 
-        let double_jeopardy_round = round_builder
-            .set_questions(double_jeopardy_questions)
-            .set_round(Round::DoubleJeopardy)
-            .build()
-            .expect("Could not build double jeopardy round from the provided data");
+        nicknames
+            .iter()
+            .enumerate()
+            .filter_map(|(column, nickname)| {
+                let score = scores?.get(column)?;
 
-        let final_jeopardy_round = round_builder
-            .set_questions(final_jeopardy_question)
-            .set_round(Round::FinalJeopardy)
-            .build()
-            .expect("Could not build final jeopardy round from the provided data");
+                Some(Score {
+                    contestant: sanitize(&nickname.inner_html()),
+                    score: parse_amount(&score.text().collect::<String>())?,
+                    remarks: remarks
+                        .and_then(|remarks| remarks.get(column))
+                        .map(|remarks| sanitize(&remarks.inner_html())),
+                })
+            })
+            .collect()
+    }
+
+    /// Parses every round present on the page
+    ///
+    /// Some archived games are missing rounds, so only a page with no rounds at all is an error
+    fn parse_rounds(&self) -> Result<Vec<JeopardyRound>, ParserError> {
+        let mut round_builder = JeopardyRoundBuilder::new();
+        let mut rounds = Vec::new();
+
+        for round in [
+            Round::Jeopardy,
+            Round::DoubleJeopardy,
+            Round::FinalJeopardy,
+            Round::Tiebreaker,
+        ] {
+            let Some(table) = self.parse_table(round) else {
+                continue;
+            };
+
+            let questions = self.parse_questions(table, round)?;
+
+            rounds.push(
+                round_builder
+                    .set_questions(questions)
+                    .set_round(round)
+                    .set_scores(self.parse_round_scores(round))
+                    .build()
+                    .map_err(|source| ParserError::InvalidRound { round, source })?,
+            );
+        }
 
-        Ok((jeopardy_round, double_jeopardy_round, final_jeopardy_round))
+        if rounds.is_empty() {
+            return Err(ParserError::NoRounds);
+        }
+
+        Ok(rounds)
     }
 
     /// Parses categories
@@ -102,79 +377,265 @@ impl JArchiveDocumentParser {
     }
 
     /// Parses table fragment
-    fn parse_table(&self, round: Round) -> Option<ElementRef> {
+    fn parse_table(&self, round: Round) -> Option<ElementRef<'_>> {
         let table_selector = match round {
             Round::Jeopardy => Selector::parse("#jeopardy_round").unwrap(),
             Round::DoubleJeopardy => Selector::parse("#double_jeopardy_round").unwrap(),
             Round::FinalJeopardy => Selector::parse("#final_jeopardy_round").unwrap(),
+            Round::Tiebreaker => Selector::parse("#tiebreaker_round").unwrap(),
         };
 
         self.document.select(&table_selector).next()
     }
 
-    /// Calculate question value
-    fn calculate_question_value(&self, index: usize, round: Round) -> Option<u32> {
-        let mut value = match index {
-            0..=5 => 200,
-            6..=11 => 400,
-            12..=17 => 600,
-            18..=23 => 800,
-            24..=29 => 1000,
-            _ => 0,
+    /// Looks up the top row's value in the jeopardy round at the time the episode aired, assuming
+    /// current values if the air date is unknown
+    fn base_value(&self, air_date: Option<NaiveDate>) -> u32 {
+        let (_, latest) = BASE_VALUES[BASE_VALUES.len() - 1];
+
+        let Some(air_date) = air_date else {
+            return latest;
         };
 
-        if round == Round::DoubleJeopardy {
-            value = value * 2;
+        BASE_VALUES
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= (air_date.year(), air_date.month(), air_date.day()))
+            .map_or(BASE_VALUES[0].1, |(_, value)| *value)
+    }
+
+    /// Calculate question value from the clue's board row, for clues which don't display one
+    ///
+    /// Returns `None` for special events with their own board values
+    fn calculate_question_value(
+        &self,
+        row: Option<u32>,
+        round: Round,
+        air_date: Option<NaiveDate>,
+        tournament: Option<&str>,
+    ) -> Option<u32> {
+        if tournament.is_some_and(|tournament| IRREGULAR_BOARD_TOURNAMENTS.contains(&tournament)) {
+            return None;
         }
 
-        if round == Round::FinalJeopardy {
-            None
-        } else {
-            Some(value)
+        let value = row? * self.base_value(air_date);
+
+        match round {
+            Round::Jeopardy => Some(value),
+            Round::DoubleJeopardy => Some(value * 2),
+            Round::FinalJeopardy | Round::Tiebreaker => None,
         }
     }
 
-    /// Parses prompts
-    fn parse_prompts(&self, fragment: ElementRef) -> Vec<String> {
-        let question_selector = Selector::parse("td.clue_text:first-of-type").unwrap();
+    /// Parses the value shown on the board, which daily doubles replace with the wager
+    fn parse_displayed_value(&self, clue: ElementRef) -> Option<u32> {
+        let value_selector = Selector::parse("td.clue_value").unwrap();
+
+        let value = clue.select(&value_selector).next()?;
+        let amount = parse_amount(&value.text().collect::<String>())?;
+
+        u32::try_from(amount).ok()
+    }
+
+    /// Parses the board column and row out of a clue id such as `clue_J_3_2`
+    ///
+    /// Final jeopardy clues (`clue_FJ`) have no coordinates
+    fn parse_coordinates(&self, id: &str) -> Option<(u32, u32)> {
+        let mut parts = id.strip_prefix("clue_")?.split('_').skip(1);
+
+        let column = parts.next()?.parse().ok()?;
+        let row = parts.next()?.parse().ok()?;
+
+        Some((column, row))
+    }
+
+    /// Parses the prompt cell of a clue
+    fn parse_prompt<'b>(&self, clue: ElementRef<'b>) -> Option<ElementRef<'b>> {
+        let prompt_selector = Selector::parse("td.clue_text").unwrap();
+
+        clue.select(&prompt_selector)
+            .find(|prompt| !prompt.value().id().unwrap_or_default().ends_with("_r"))
+    }
+
+    /// Parses the pictures, audio and video a prompt links to, resolving relative links against
+    /// the parser's base url
+    fn parse_media(&self, prompt: ElementRef) -> Vec<MediaRef> {
+        let link_selector = Selector::parse("a[href]").unwrap();
+
+        let mut media: Vec<MediaRef> = Vec::new();
+
+        for link in prompt.select(&link_selector) {
+            let Some(url) = link
+                .value()
+                .attr("href")
+                .and_then(|href| self.base_url.join(href.trim()).ok())
+            else {
+                continue;
+            };
+
+            let media_ref = MediaRef::new(url.to_string());
+
+            // prompts also link to ordinary web pages, which aren't part of the clue
+            if !url.path().starts_with("/media/") && media_ref.kind == MediaKind::Other {
+                continue;
+            }
+
+            if !media.contains(&media_ref) {
+                media.push(media_ref);
+            }
+        }
+
+        media
+    }
+
+    /// Parses the cells of every revealed clue, in the same order as the prompts
+    fn parse_clues<'b>(&self, fragment: ElementRef<'b>) -> Vec<ElementRef<'b>> {
+        let clue_selector = Selector::parse("td.clue").unwrap();
+        let clue_text_selector = Selector::parse("td.clue_text").unwrap();
 
         fragment
-            .select(&question_selector)
-            .map(|c| c.inner_html())
+            .select(&clue_selector)
+            .filter(|clue| clue.select(&clue_text_selector).next().is_some())
             .collect()
     }
 
-    /// Parses raw jarchive HTML data into structured objects
-    fn parse_questions(&self, round: Round) -> Result<Vec<JeopardyQuestion>, Error> {
-        let Some(table) = self.parse_table(round) else {
-            return Err(Error::Static("Could not locate jeopardy table"));
-        };
+    /// Parses when the clue was picked within its round
+    fn parse_order(&self, clue: ElementRef) -> Option<u32> {
+        let order_selector = Selector::parse("td.clue_order_number").unwrap();
+
+        clue.select(&order_selector)
+            .next()?
+            .text()
+            .collect::<String>()
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    /// Parses the wager of a daily double clue, or `None` if the clue is not a daily double
+    fn parse_daily_double_wager(&self, clue: ElementRef) -> Option<u32> {
+        let daily_double_selector = Selector::parse("td.clue_value_daily_double").unwrap();
+
+        let wager = clue.select(&daily_double_selector).next()?;
+        let amount = parse_amount(&wager.text().collect::<String>())?;
+
+        u32::try_from(amount).ok()
+    }
+
+    /// Parses each contestant's final jeopardy response and wager
+    ///
+    /// Each contestant takes up two rows of the response table: their nickname (classed `right`
+    /// or `wrong`) next to their response, followed by their wager
+    fn parse_final_wagers(&self, fragment: ElementRef) -> Vec<FinalJeopardyWager> {
+        let row_selector = Selector::parse(r#"td[id$="_r"] table tr"#).unwrap();
+        let cell_selector = Selector::parse("td").unwrap();
+
+        let rows: Vec<ElementRef> = fragment.select(&row_selector).collect();
+
+        rows.chunks(2)
+            .filter_map(|rows| {
+                let mut cells = rows[0].select(&cell_selector);
+
+                let contestant = cells.next()?;
+                let classes: Vec<&str> = contestant.value().classes().collect();
+                let correct = classes.contains(&"right");
+
+                if !correct && !classes.contains(&"wrong") {
+                    return None;
+                }
+
+                let response = cells.next().map(|cell| cell.inner_html());
+                let wager = rows
+                    .get(1)
+                    .and_then(|row| row.select(&cell_selector).next())
+                    .and_then(|cell| parse_amount(&cell.text().collect::<String>()))
+                    .and_then(|amount| u32::try_from(amount).ok());
+
+                Some(FinalJeopardyWager {
+                    contestant: contestant.inner_html(),
+                    response,
+                    correct,
+                    wager,
+                })
+            })
+            .collect()
+    }
 
+    /// Parses raw jarchive HTML data into structured objects
+    ///
+    /// Clues are located by their grid coordinates rather than their position on the page, since
+    /// unrevealed clues leave gaps in the board
+    fn parse_questions(
+        &self,
+        table: ElementRef,
+        round: Round,
+    ) -> Result<Vec<JeopardyQuestion>, ParserError> {
         let categories = self.parse_categories(table);
-        let prompts = self.parse_prompts(table);
+        let air_date = self.parse_air_date();
+        let tournament = self.parse_comments().as_deref().and_then(tournament_label);
 
         let mut jeopardy_questions: Vec<JeopardyQuestion> = Vec::new();
 
-        for i in 0..prompts.len() {
-            let prompt = &prompts[i];
-            let category = &categories[if categories.len() == 1 {
-                0
+        for clue in self.parse_clues(table) {
+            let Some(prompt) = self.parse_prompt(clue) else {
+                continue;
+            };
+
+            let coordinates = prompt
+                .value()
+                .id()
+                .and_then(|id| self.parse_coordinates(id));
+            let column = coordinates.map(|(column, _)| column);
+            let row = coordinates.map(|(_, row)| row);
+
+            let category = match column {
+                Some(column) => (column as usize)
+                    .checked_sub(1)
+                    .and_then(|index| categories.get(index)),
+                None => categories.first(),
+            };
+            let Some(category) = category else {
+                return Err(ParserError::MissingCategory { round, column });
+            };
+
+            let answer = self.parse_answer(clue);
+            let (responses, stumper_marked) = self.parse_responses(clue);
+            let triple_stumper = stumper_marked
+                || (round == Round::FinalJeopardy
+                    && !responses.is_empty()
+                    && !responses.iter().any(|response| response.correct));
+            let value = self.parse_displayed_value(clue).or_else(|| {
+                self.calculate_question_value(row, round, air_date, tournament.as_deref())
+            });
+            let order = self.parse_order(clue);
+            let wager = self.parse_daily_double_wager(clue);
+            let media = self.parse_media(prompt);
+            let final_wagers = if round == Round::FinalJeopardy {
+                self.parse_final_wagers(clue)
             } else {
-                i.rem_euclid(NUM_CATEGORIES)
-            }];
-            let answer = self.parse_answer(table, i, round);
-            let value = self.calculate_question_value(i, round);
+                Vec::new()
+            };
 
             let question = JeopardyQuestionBuilder::new()
                 .set_answer(answer)
                 .set_category(category)
-                .set_prompt(prompt)
+                .set_prompt(prompt.inner_html())
                 .set_round(round)
                 .set_value(value)
+                .set_row(row)
+                .set_column(column)
+                .set_order(order)
+                .set_daily_double(wager.is_some())
+                .set_wager(wager)
+                .set_final_wagers(final_wagers)
+                .set_responses(responses)
+                .set_triple_stumper(triple_stumper)
+                .set_flags(CLASSIFIER.classify(&prompt.inner_html(), &media))
+                .set_media(media)
                 .build()
-                .expect("Could not build jeopardy question model");
+                .map_err(|source| ParserError::InvalidQuestion { round, source })?;
 
-            let question = question.sanitize();
+            let question = question.sanitize_as(self.text_format);
 
             jeopardy_questions.push(question);
         }
@@ -182,16 +643,759 @@ impl JArchiveDocumentParser {
         Ok(jeopardy_questions)
     }
 
-    /// Parses an answer string from an element ref
-    /// Note: For some reason unknown to me the regex crate does not support lookaheads...
-    /// Just match this for now and we can strip off the values using string magic
-    fn parse_answer(&self, fragment: ElementRef, index: usize, _round: Round) -> Option<String> {
+    /// Parses the correct response to a clue
+    fn parse_answer(&self, clue: ElementRef) -> Option<String> {
         let correct_response_selector =
             Selector::parse(".correct_response").expect("Failed to parse selector");
 
-        fragment
-            .select(&correct_response_selector)
-            .nth(index)
-            .map(|element| element.text().collect::<Vec<_>>().join(""))
+        clue.select(&correct_response_selector)
+            .next()
+            .map(|element| element.inner_html())
+    }
+
+    /// Parses who attempted a clue and whether they were right, along with whether j-archive
+    /// marked the clue as a triple stumper
+    fn parse_responses(&self, clue: ElementRef) -> (Vec<ClueResponse>, bool) {
+        let response_selector =
+            Selector::parse(r#"td[id$="_r"] td.right, td[id$="_r"] td.wrong"#).unwrap();
+
+        let mut stumper_marked = false;
+        let mut responses = Vec::new();
+
+        for cell in clue.select(&response_selector) {
+            let contestant = cell.inner_html();
+
+            if contestant.trim().ends_with("Stumper") {
+                stumper_marked = true;
+                continue;
+            }
+
+            responses.push(ClueResponse {
+                contestant,
+                correct: cell.value().classes().any(|class| class == "right"),
+            });
+        }
+
+        (responses, stumper_marked)
+    }
+}
+
+/// Finds the special event, if any, that a game's comments say it was part of
+fn tournament_label(comments: &str) -> Option<String> {
+    let comments = comments.to_lowercase();
+
+    TOURNAMENTS
+        .iter()
+        .find(|tournament| comments.contains(&tournament.to_lowercase()))
+        .map(|tournament| tournament.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use scraper::Html;
+
+    use super::{tournament_label, JArchiveDocumentParser, TextFormat};
+    use crate::models::{
+        flag::ClueFlag,
+        media::{MediaKind, MediaRef},
+        question::Round,
+        round::JeopardyRound,
+    };
+
+    const FIXTURE: &str = include_str!("../../fixtures/synthetic_game.html");
+
+    /// A game where several clues were never revealed
+    const UNREVEALED_FIXTURE: &str = include_str!("../../fixtures/synthetic_unrevealed.html");
+
+    /// A game from before clue values were doubled in November 2001
+    const CLASSIC_FIXTURE: &str = include_str!("../../fixtures/synthetic_classic.html");
+
+    /// A game decided by a tiebreaker clue
+    const TIEBREAKER_FIXTURE: &str = include_str!("../../fixtures/synthetic_tiebreaker.html");
+
+    /// A game with no double jeopardy round archived
+    const PARTIAL_FIXTURE: &str = include_str!("../../fixtures/synthetic_partial.html");
+
+    fn parse_fixture() -> crate::models::episode::JeopardyEpisode {
+        JArchiveDocumentParser::new(Html::parse_document(FIXTURE), 7515)
+            .parse()
+            .unwrap()
+    }
+
+    /// Splits a standard game into its jeopardy, double jeopardy and final jeopardy rounds
+    fn standard_rounds(
+        episode: crate::models::episode::JeopardyEpisode,
+    ) -> (JeopardyRound, JeopardyRound, JeopardyRound) {
+        let [jeopardy, double_jeopardy, final_jeopardy]: [JeopardyRound; 3] =
+            episode.rounds.try_into().unwrap();
+
+        (jeopardy, double_jeopardy, final_jeopardy)
+    }
+
+    fn parse_unrevealed_fixture() -> crate::models::episode::JeopardyEpisode {
+        JArchiveDocumentParser::new(Html::parse_document(UNREVEALED_FIXTURE), 6942)
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn parses_episode_metadata() {
+        let episode = parse_fixture();
+
+        assert_eq!(episode.id, 7515);
+        assert_eq!(episode.air_date, NaiveDate::from_ymd_opt(2022, 11, 17));
+        assert_eq!(episode.show_number, Some(8745));
+        assert_eq!(episode.comments, None);
+        assert_eq!(episode.tournament, None);
+    }
+
+    #[test]
+    fn writes_air_date_in_iso_format() {
+        let json = serde_json::to_value(parse_fixture()).unwrap();
+
+        assert_eq!(json["air_date"], "2022-11-17");
+    }
+
+    #[test]
+    fn parses_game_comments() {
+        let episode = JArchiveDocumentParser::new(Html::parse_document(TIEBREAKER_FIXTURE), 7012)
+            .parse()
+            .unwrap();
+
+        assert_eq!(episode.show_number, Some(8391));
+        assert_eq!(
+            episode.comments.as_deref(),
+            Some("Tournament of Champions quarterfinal game 3.")
+        );
+        assert_eq!(
+            episode.tournament.as_deref(),
+            Some("Tournament of Champions")
+        );
+    }
+
+    #[test]
+    fn labels_tournaments() {
+        assert_eq!(
+            tournament_label("Ultimate Tournament of Champions first round game 2.").as_deref(),
+            Some("Ultimate Tournament of Champions")
+        );
+        assert_eq!(
+            tournament_label("2023 National College Championship final game 1.").as_deref(),
+            Some("National College Championship")
+        );
+        assert_eq!(tournament_label("Kate Sullivan game 3.").as_deref(), None);
+    }
+
+    #[test]
+    fn parses_every_round() {
+        let (jeopardy, double_jeopardy, final_jeopardy) = standard_rounds(parse_fixture());
+
+        assert_eq!(jeopardy.round, Round::Jeopardy);
+        assert_eq!(jeopardy.questions.len(), 30);
+        assert_eq!(double_jeopardy.round, Round::DoubleJeopardy);
+        assert_eq!(double_jeopardy.questions.len(), 30);
+        assert_eq!(final_jeopardy.round, Round::FinalJeopardy);
+        assert_eq!(final_jeopardy.questions.len(), 1);
+    }
+
+    #[test]
+    fn parses_questions() {
+        let (jeopardy, double_jeopardy, final_jeopardy) = standard_rounds(parse_fixture());
+
+        let first = &jeopardy.questions[0];
+        assert_eq!(first.category, "HISTORIC DATES");
+        assert_eq!(first.value, Some(200));
+        assert_eq!(first.answer.as_deref(), Some("Plymouth Rock"));
+
+        let last = &double_jeopardy.questions[29];
+        assert_eq!(last.category, "BEFORE & AFTER");
+        assert_eq!(last.value, Some(2000));
+        assert_eq!(last.answer.as_deref(), Some("Hamlet Danish"));
+
+        let final_question = &final_jeopardy.questions[0];
+        assert_eq!(final_question.category, "MOVIES & LITERATURE");
+        assert_eq!(final_question.value, None);
+        assert_eq!(final_question.answer.as_deref(), Some("Joseph Conrad"));
+    }
+
+    #[test]
+    fn parses_daily_double_wagers() {
+        let (jeopardy, double_jeopardy, _) = standard_rounds(parse_fixture());
+
+        let daily_doubles = |questions: &[crate::models::question::JeopardyQuestion]| {
+            questions
+                .iter()
+                .enumerate()
+                .filter(|(_, question)| question.daily_double)
+                .map(|(i, question)| (i, question.wager))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(daily_doubles(&jeopardy.questions), vec![(20, Some(1000))]);
+        assert_eq!(
+            daily_doubles(&double_jeopardy.questions),
+            vec![(18, Some(2000)), (28, Some(1500))]
+        );
+        assert_eq!(jeopardy.questions[20].value, Some(800));
+        assert_eq!(jeopardy.questions[0].wager, None);
+    }
+
+    #[test]
+    fn parses_final_jeopardy_wagers() {
+        let (_, _, final_jeopardy) = standard_rounds(parse_fixture());
+
+        let wagers = &final_jeopardy.questions[0].final_wagers;
+        let summary = wagers
+            .iter()
+            .map(|wager| {
+                (
+                    wager.contestant.as_str(),
+                    wager.response.as_deref(),
+                    wager.correct,
+                    wager.wager,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("Mark", Some("Who is Kipling?"), false, Some(1000)),
+                ("Priya", Some("Who is Conrad?"), true, Some(6300)),
+                ("Kate", Some("Who is Melville?"), false, Some(3500)),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_clue_coordinates() {
+        let (jeopardy, _, final_jeopardy) = standard_rounds(parse_fixture());
+
+        let first = &jeopardy.questions[0];
+        assert_eq!((first.column, first.row), (Some(1), Some(1)));
+
+        let last = &jeopardy.questions[29];
+        assert_eq!((last.column, last.row), (Some(6), Some(5)));
+
+        let final_question = &final_jeopardy.questions[0];
+        assert_eq!((final_question.column, final_question.row), (None, None));
+    }
+
+    #[test]
+    fn skips_unrevealed_clues() {
+        let (jeopardy, double_jeopardy, final_jeopardy) =
+            standard_rounds(parse_unrevealed_fixture());
+
+        let coordinates = |questions: &[crate::models::question::JeopardyQuestion]| {
+            questions
+                .iter()
+                .map(|question| (question.column.unwrap(), question.row.unwrap()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(jeopardy.questions.len(), 27);
+        assert!(!coordinates(&jeopardy.questions).contains(&(2, 4)));
+        assert!(!coordinates(&jeopardy.questions).contains(&(5, 5)));
+
+        assert_eq!(double_jeopardy.questions.len(), 27);
+        assert!(!coordinates(&double_jeopardy.questions).contains(&(1, 5)));
+
+        assert_eq!(final_jeopardy.questions.len(), 1);
+    }
+
+    #[test]
+    fn labels_clues_after_gaps_correctly() {
+        let (jeopardy, double_jeopardy, _) = standard_rounds(parse_unrevealed_fixture());
+
+        // the clue straight after the unrevealed one in the second column
+        let after_gap = jeopardy
+            .questions
+            .iter()
+            .find(|question| question.column == Some(3) && question.row == Some(4))
+            .unwrap();
+        assert_eq!(after_gap.category, "RHYME TIME");
+        assert_eq!(after_gap.value, Some(800));
+        assert_eq!(after_gap.answer.as_deref(), Some("J 3-4"));
+
+        let last = jeopardy.questions.last().unwrap();
+        assert_eq!(last.category, "SPORTS");
+        assert_eq!(last.value, Some(1000));
+        assert_eq!(last.answer.as_deref(), Some("J 4-5"));
+
+        let categories = ["ART", "GEOGRAPHY", "SCIENCE", "TV SHOWS", "FOOD", "OPERA"];
+        for question in &double_jeopardy.questions {
+            let (column, row) = (question.column.unwrap(), question.row.unwrap());
+
+            assert_eq!(question.category, categories[column as usize - 1]);
+            assert_eq!(question.value, Some(row * 400));
+            assert_eq!(
+                question.answer.as_deref(),
+                Some(format!("DJ {0}-{1}", column, row).as_str())
+            );
+        }
+    }
+
+    #[test]
+    fn parses_daily_doubles_on_boards_with_gaps() {
+        let (jeopardy, double_jeopardy, _) = standard_rounds(parse_unrevealed_fixture());
+
+        let daily_doubles = |questions: &[crate::models::question::JeopardyQuestion]| {
+            questions
+                .iter()
+                .filter(|question| question.daily_double)
+                .map(|question| (question.column, question.row, question.wager))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            daily_doubles(&jeopardy.questions),
+            vec![(Some(4), Some(3), Some(1200))]
+        );
+        assert_eq!(
+            daily_doubles(&double_jeopardy.questions),
+            vec![
+                (Some(5), Some(3), Some(2000)),
+                (Some(2), Some(4), Some(3000))
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_displayed_clue_values() {
+        let episode = JArchiveDocumentParser::new(Html::parse_document(CLASSIC_FIXTURE), 3320)
+            .parse()
+            .unwrap();
+        let (jeopardy, double_jeopardy, _) = standard_rounds(episode);
+
+        for question in jeopardy.questions.iter().chain(&double_jeopardy.questions) {
+            let base = if question.round == Round::Jeopardy {
+                100
+            } else {
+                200
+            };
+
+            assert_eq!(question.value, Some(question.row.unwrap() * base));
+        }
+    }
+
+    #[test]
+    fn values_daily_doubles_by_era() {
+        let episode = JArchiveDocumentParser::new(Html::parse_document(CLASSIC_FIXTURE), 3320)
+            .parse()
+            .unwrap();
+        let (jeopardy, double_jeopardy, _) = standard_rounds(episode);
+
+        let daily_double = jeopardy.questions.iter().find(|q| q.daily_double).unwrap();
+        assert_eq!((daily_double.row, daily_double.value), (Some(3), Some(300)));
+        assert_eq!(daily_double.wager, Some(500));
+
+        let values = double_jeopardy
+            .questions
+            .iter()
+            .filter(|question| question.daily_double)
+            .map(|question| question.value)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![Some(400), Some(800)]);
+
+        let modern_daily_double = &parse_fixture().rounds[0].questions[20];
+        assert_eq!(modern_daily_double.value, Some(800));
+    }
+
+    #[test]
+    fn falls_back_to_era_values() {
+        let parser = JArchiveDocumentParser::new(Html::parse_document(""), 1);
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day);
+
+        let value =
+            |round, air_date| parser.calculate_question_value(Some(3), round, air_date, None);
+
+        assert_eq!(value(Round::Jeopardy, date(2001, 11, 23)), Some(300));
+        assert_eq!(value(Round::DoubleJeopardy, date(2001, 11, 23)), Some(600));
+        assert_eq!(value(Round::Jeopardy, date(2001, 11, 26)), Some(600));
+        assert_eq!(value(Round::DoubleJeopardy, date(2022, 11, 17)), Some(1200));
+        assert_eq!(value(Round::Jeopardy, None), Some(600));
+        assert_eq!(value(Round::FinalJeopardy, date(1999, 6, 15)), None);
+    }
+
+    #[test]
+    fn does_not_guess_values_for_special_formats() {
+        let parser = JArchiveDocumentParser::new(Html::parse_document(""), 1);
+        let air_date = NaiveDate::from_ymd_opt(2022, 11, 17);
+
+        let value = |tournament| {
+            parser.calculate_question_value(Some(3), Round::Jeopardy, air_date, tournament)
+        };
+
+        assert_eq!(value(Some("Celebrity Jeopardy!")), None);
+        assert_eq!(value(Some("Kids Week")), None);
+        assert_eq!(value(Some("Super Jeopardy!")), None);
+        assert_eq!(value(Some("Tournament of Champions")), Some(600));
+        assert_eq!(
+            tournament_label("Super Jeopardy! quarterfinal game 4.").as_deref(),
+            Some("Super Jeopardy!")
+        );
+    }
+
+    #[test]
+    fn parses_contestants() {
+        let contestants = parse_fixture().contestants;
+
+        assert_eq!(contestants.len(), 3);
+
+        let challenger = &contestants[0];
+        assert_eq!(challenger.name, "Mark Dwyer");
+        assert_eq!(challenger.player_id, Some(13402));
+        assert_eq!(
+            challenger.occupation.as_deref(),
+            Some("high school history teacher")
+        );
+        assert_eq!(challenger.hometown.as_deref(), Some("Tulsa, Oklahoma"));
+        assert!(!challenger.returning_champion);
+        assert_eq!(challenger.days_as_champion, None);
+
+        let champion = &contestants[2];
+        assert_eq!(champion.name, "Kate Sullivan");
+        assert_eq!(champion.occupation.as_deref(), Some("librarian"));
+        assert_eq!(champion.hometown.as_deref(), Some("Portland, Maine"));
+        assert!(champion.returning_champion);
+        assert_eq!(champion.days_as_champion, Some(2));
+        assert_eq!(champion.winnings, Some(41801));
+    }
+
+    #[test]
+    fn parses_linked_media() {
+        let episode = parse_fixture();
+        let question = |column, row| {
+            episode.rounds[0]
+                .questions
+                .iter()
+                .find(|question| question.column == Some(column) && question.row == Some(row))
+                .unwrap()
+        };
+
+        assert_eq!(
+            question(3, 1).media,
+            vec![MediaRef {
+                url: "https://www.j-archive.com/media/2022-11-17_J_11.jpg".to_string(),
+                kind: MediaKind::Image,
+            }]
+        );
+        assert_eq!(question(4, 4).media[0].kind, MediaKind::Video);
+        assert!(question(1, 1).media.is_empty());
+    }
+
+    #[test]
+    fn flags_clues_depending_on_context() {
+        let episode = parse_fixture();
+
+        let flagged = episode
+            .rounds
+            .iter()
+            .flat_map(|round| &round.questions)
+            .filter(|question| !question.flags.is_empty())
+            .map(|question| (question.column, question.row, question.flags.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            flagged,
+            vec![
+                (Some(3), Some(1), vec![ClueFlag::RequiresMedia]),
+                (
+                    Some(4),
+                    Some(4),
+                    vec![ClueFlag::RequiresMedia, ClueFlag::HasClueCrew]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolves_relative_media_links() {
+        let parser = JArchiveDocumentParser::new(Html::parse_document(""), 1);
+        let document = Html::parse_fragment(
+            r#"<table><tr><td class="clue_text"><a href="media/1999-06-15_J_5.mp3">Heard here</a>, <a href="media/1999-06-15_J_5.mp3">again</a> in <a href="https://en.wikipedia.org/wiki/Paris">this city</a></td></tr></table>"#,
+        );
+        let prompt = document
+            .select(&scraper::Selector::parse("td").unwrap())
+            .next()
+            .unwrap();
+
+        let media = parser.parse_media(prompt);
+
+        assert_eq!(media.len(), 1);
+        assert_eq!(
+            media[0].url,
+            "https://www.j-archive.com/media/1999-06-15_J_5.mp3"
+        );
+        assert_eq!(media[0].kind, MediaKind::Audio);
+    }
+
+    #[test]
+    fn resolves_relative_media_links_against_base_url() {
+        let mut parser = JArchiveDocumentParser::new(Html::parse_document(""), 1);
+        parser.set_base_url(reqwest::Url::parse("http://localhost:8080/mirror/").unwrap());
+
+        let document = Html::parse_fragment(
+            r#"<table><tr><td class="clue_text"><a href="media/1999-06-15_J_5.mp3">Heard here</a></td></tr></table>"#,
+        );
+        let prompt = document
+            .select(&scraper::Selector::parse("td").unwrap())
+            .next()
+            .unwrap();
+
+        assert_eq!(
+            parser.parse_media(prompt)[0].url,
+            "http://localhost:8080/mirror/media/1999-06-15_J_5.mp3"
+        );
+    }
+
+    #[test]
+    fn keeps_answer_formatting_on_request() {
+        let answer = |text_format| {
+            let episode = JArchiveDocumentParser::new(Html::parse_document(FIXTURE), 7515)
+                .set_text_format(text_format)
+                .parse()
+                .unwrap();
+
+            episode.rounds[0]
+                .questions
+                .iter()
+                .find(|question| question.column == Some(1) && question.row == Some(4))
+                .and_then(|question| question.answer.clone())
+                .unwrap()
+        };
+
+        assert_eq!(answer(TextFormat::Plain), "the Titanic");
+        assert_eq!(answer(TextFormat::Markdown), "the *Titanic*");
+        assert_eq!(answer(TextFormat::Html), "the <i>Titanic</i>");
+    }
+
+    #[test]
+    fn finds_a_players_responses() {
+        let episode = parse_fixture();
+
+        let responses = episode.responses_by(13403);
+        let correct = responses.iter().filter(|(_, correct)| *correct).count();
+
+        assert_eq!((responses.len(), correct), (27, 22));
+        assert!(episode.responses_by(1).is_empty());
+    }
+
+    #[test]
+    fn finds_responses_by_score_table_nickname() {
+        let mut episode = parse_fixture();
+
+        // e.g. a player who goes by a shortened middle name
+        episode.contestants[1].name = "Anjali Priya Raman".to_string();
+
+        assert_eq!(episode.nickname(13403), Some("Priya"));
+        assert_eq!(episode.responses_by(13403).len(), 27);
+    }
+
+    #[test]
+    fn parses_final_scores() {
+        let final_scores = parse_fixture().final_scores;
+
+        let summary = final_scores
+            .iter()
+            .map(|score| {
+                (
+                    score.contestant.as_str(),
+                    score.score,
+                    score.remarks.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("Mark", 9900, Some("3rd place: $1,000")),
+                ("Priya", 18900, Some("New champion: $18,900")),
+                ("Kate", 10500, Some("2nd place: $2,000")),
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_score_table_cells_by_column() {
+        let document = Html::parse_document(
+            r#"<table>
+<tr><td class="score_player_nickname">Mark</td><td class="score_player_nickname">Priya</td><td class="score_player_nickname">Kate</td></tr>
+<tr><td class="score_positive">$9,900</td><td></td><td class="score_positive">$10,500</td></tr>
+<tr><td class="score_remarks">3rd place: $1,000</td><td class="score_remarks">New champion</td><td class="score_remarks">2nd place: $2,000</td></tr>
+</table>"#,
+        );
+        let parser = JArchiveDocumentParser::new(Html::parse_document(""), 1);
+        let table = document
+            .select(&scraper::Selector::parse("table").unwrap())
+            .next()
+            .unwrap();
+
+        let summary = parser
+            .parse_score_table(table)
+            .into_iter()
+            .map(|score| (score.contestant, score.score, score.remarks))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "Mark".to_string(),
+                    9900,
+                    Some("3rd place: $1,000".to_string())
+                ),
+                (
+                    "Kate".to_string(),
+                    10500,
+                    Some("2nd place: $2,000".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_responses() {
+        let (jeopardy, _, _) = standard_rounds(parse_fixture());
+
+        let responses = |question: &crate::models::question::JeopardyQuestion| {
+            question
+                .responses
+                .iter()
+                .map(|response| (response.contestant.clone(), response.correct))
+                .collect::<Vec<_>>()
+        };
+
+        let first = &jeopardy.questions[0];
+        assert_eq!(
+            responses(first),
+            vec![("Kate".to_string(), false), ("Mark".to_string(), true)]
+        );
+        assert!(!first.triple_stumper);
+
+        // column 2, row 5
+        let stumper = &jeopardy.questions[25];
+        assert_eq!(
+            responses(stumper),
+            vec![("Priya".to_string(), false), ("Kate".to_string(), false)]
+        );
+        assert!(stumper.triple_stumper);
+    }
+
+    #[test]
+    fn flags_triple_stumpers() {
+        let (jeopardy, double_jeopardy, final_jeopardy) = standard_rounds(parse_fixture());
+
+        let stumpers = |questions: &[crate::models::question::JeopardyQuestion]| {
+            questions
+                .iter()
+                .filter(|question| question.triple_stumper)
+                .map(|question| (question.column.unwrap(), question.row.unwrap()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(stumpers(&jeopardy.questions), vec![(6, 4), (2, 5)]);
+
+        // a missed daily double only stumped one contestant
+        assert_eq!(stumpers(&double_jeopardy.questions), vec![(6, 5)]);
+        assert_eq!(
+            double_jeopardy.questions[28].responses[0].contestant,
+            "Mark"
+        );
+
+        let final_question = &final_jeopardy.questions[0];
+        assert_eq!(final_question.responses.len(), 3);
+        assert!(!final_question.triple_stumper);
+    }
+
+    #[test]
+    fn parses_clue_order() {
+        let (jeopardy, double_jeopardy, final_jeopardy) = standard_rounds(parse_fixture());
+
+        assert_eq!(jeopardy.questions[0].order, Some(2));
+        assert_eq!(jeopardy.questions[1].order, Some(4));
+
+        for round in [&jeopardy, &double_jeopardy] {
+            let mut orders = round
+                .questions
+                .iter()
+                .map(|question| question.order.unwrap())
+                .collect::<Vec<_>>();
+            orders.sort_unstable();
+
+            assert_eq!(orders, (1..=30).collect::<Vec<_>>());
+        }
+
+        assert_eq!(final_jeopardy.questions[0].order, None);
+    }
+
+    #[test]
+    fn parses_clue_order_on_boards_with_gaps() {
+        let (jeopardy, _, _) = standard_rounds(parse_unrevealed_fixture());
+
+        let last_picked = jeopardy
+            .questions
+            .iter()
+            .max_by_key(|question| question.order)
+            .unwrap();
+
+        assert_eq!(last_picked.order, Some(27));
+    }
+
+    #[test]
+    fn parses_tiebreaker_rounds() {
+        let episode = JArchiveDocumentParser::new(Html::parse_document(TIEBREAKER_FIXTURE), 7012)
+            .parse()
+            .unwrap();
+
+        let rounds = episode
+            .rounds
+            .iter()
+            .map(|round| round.round)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rounds,
+            vec![
+                Round::Jeopardy,
+                Round::DoubleJeopardy,
+                Round::FinalJeopardy,
+                Round::Tiebreaker
+            ]
+        );
+        assert!(episode.complete);
+
+        let tiebreaker = &episode.round(Round::Tiebreaker).unwrap().questions[0];
+        assert_eq!(tiebreaker.category, "ANIMALS");
+        assert_eq!(tiebreaker.answer.as_deref(), Some("a jaguar"));
+        assert_eq!(tiebreaker.value, None);
+        assert_eq!(tiebreaker.responses.len(), 2);
+    }
+
+    #[test]
+    fn parses_partial_games() {
+        let episode = JArchiveDocumentParser::new(Html::parse_document(PARTIAL_FIXTURE), 4305)
+            .parse()
+            .unwrap();
+
+        assert!(!episode.complete);
+        assert!(episode.round(Round::DoubleJeopardy).is_none());
+        assert_eq!(episode.round(Round::Jeopardy).unwrap().questions.len(), 29);
+        assert_eq!(
+            episode.round(Round::FinalJeopardy).unwrap().questions[0]
+                .answer
+                .as_deref(),
+            Some("Maine")
+        );
+    }
+
+    #[test]
+    fn rejects_pages_without_rounds() {
+        let html = "<p>ERROR: No game 99999 in database.</p>";
+
+        let result = JArchiveDocumentParser::new(Html::parse_document(html), 99999).parse();
+
+        assert!(matches!(result, Err(super::ParserError::NoRounds)));
     }
 }
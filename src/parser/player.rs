@@ -0,0 +1,102 @@
+use regex::Regex;
+use scraper::{Html, Selector};
+
+use super::season::parse_game_link;
+use crate::models::player::{Player, PlayerAppearance};
+use crate::utils::{currency::parse_amount, sanitizer::sanitize};
+
+/// Parses a player's page (`showplayer.php`), with their games in the order they aired
+///
+/// Returns `None` if the page doesn't describe a player, which is what j-archive serves for
+/// unknown ids.
+pub fn parse_player(raw_html: &str, player_id: u32) -> Option<Player> {
+    let document = Html::parse_document(raw_html);
+
+    let name_selector = Selector::parse("p.player_full_name").unwrap();
+    let bio_selector = Selector::parse("p.player_occupation_and_origin").unwrap();
+    let row_selector = Selector::parse("tr").unwrap();
+    let link_selector = Selector::parse(r#"a[href*="showgame.php?game_id="]"#).unwrap();
+
+    let final_score_pattern = Regex::new(r"Final score:\s*(-?\$[\d,]+)").unwrap();
+    let winnings_pattern = Regex::new(r"total winnings of (\$[\d,]+)").unwrap();
+
+    let name = document
+        .select(&name_selector)
+        .next()
+        .map(|name| sanitize(&name.inner_html()))
+        .filter(|name| !name.is_empty())?;
+
+    let bio = document
+        .select(&bio_selector)
+        .next()
+        .map(|bio| sanitize(&bio.inner_html()))
+        .filter(|bio| !bio.is_empty());
+
+    let text = document.root_element().text().collect::<String>();
+    let winnings = winnings_pattern
+        .captures(&text)
+        .and_then(|winnings| parse_amount(&winnings[1]))
+        .and_then(|winnings| u32::try_from(winnings).ok());
+
+    let mut appearances: Vec<PlayerAppearance> = document
+        .select(&row_selector)
+        .filter_map(|row| {
+            let game = parse_game_link(row.select(&link_selector).next()?)?;
+
+            Some(PlayerAppearance {
+                game_id: game.game_id,
+                show_number: game.show_number,
+                air_date: game.air_date,
+                final_score: final_score_pattern
+                    .captures(&row.text().collect::<String>())
+                    .and_then(|final_score| parse_amount(&final_score[1])),
+            })
+        })
+        .collect();
+
+    appearances.sort_by_key(|appearance| (appearance.air_date, appearance.game_id));
+    appearances.dedup_by_key(|appearance| appearance.game_id);
+
+    Some(Player {
+        id: player_id,
+        name,
+        bio,
+        winnings,
+        appearances,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::parse_player;
+
+    #[test]
+    fn parses_player_page() {
+        let player =
+            parse_player(include_str!("../../fixtures/synthetic_player.html"), 13403).unwrap();
+
+        assert_eq!(player.name, "Priya Raman");
+        assert_eq!(
+            player.bio.as_deref(),
+            Some("a software engineer from Austin, Texas")
+        );
+        assert_eq!(player.winnings, Some(41300));
+        assert_eq!(player.episode_ids(), vec![7515, 7516, 7517]);
+
+        let debut = &player.appearances[0];
+        assert_eq!(debut.show_number, Some(8745));
+        assert_eq!(debut.air_date, NaiveDate::from_ymd_opt(2022, 11, 17));
+        assert_eq!(debut.final_score, Some(18900));
+        assert_eq!(player.appearances[2].final_score, Some(0));
+    }
+
+    #[test]
+    fn ignores_pages_without_a_player() {
+        assert_eq!(
+            parse_player("<html><body><p>ERROR: No player</p></body></html>", 1),
+            None
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use std::sync::LazyLock;
+
+use chrono::NaiveDate;
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::models::season::{Season, SeasonGame};
+
+/// Matches the description of a game link, e.g. "#8745, aired 2022-11-17"
+static GAME_DESCRIPTION_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"#(\d+),\s*aired\s*(\d{4}-\d{2}-\d{2})").unwrap());
+
+/// Parses j-archive's season index (`listseasons.php`), oldest season first
+pub fn parse_seasons(raw_html: &str) -> Vec<Season> {
+    let document = Html::parse_document(raw_html);
+
+    let row_selector = Selector::parse("tr").unwrap();
+    let link_selector = Selector::parse(r#"a[href*="showseason.php?season="]"#).unwrap();
+
+    let date_range_pattern = Regex::new(r"(\d{4}-\d{2}-\d{2})\s+to\s+(\d{4}-\d{2}-\d{2})").unwrap();
+
+    let mut seasons: Vec<Season> = document
+        .select(&row_selector)
+        .filter_map(|row| {
+            let link = row.select(&link_selector).next()?;
+            let id = link.value().attr("href")?.split("season=").nth(1)?;
+
+            let text = row.text().collect::<String>();
+            let date_range = date_range_pattern.captures(&text);
+            let date = |index| {
+                date_range
+                    .as_ref()
+                    .and_then(|date_range| parse_iso_date(&date_range[index]))
+            };
+
+            Some(Season {
+                id: id.to_string(),
+                name: link.text().collect::<String>().trim().to_string(),
+                first_aired: date(1),
+                last_aired: date(2),
+            })
+        })
+        .collect();
+
+    seasons.reverse();
+
+    seasons
+}
+
+/// Parses a season's page (`showseason.php`), in the order the games aired
+pub fn parse_season_games(raw_html: &str) -> Vec<SeasonGame> {
+    let document = Html::parse_document(raw_html);
+
+    let link_selector = Selector::parse(r#"a[href*="showgame.php?game_id="]"#).unwrap();
+
+    let mut games: Vec<SeasonGame> = document
+        .select(&link_selector)
+        .filter_map(parse_game_link)
+        .collect();
+
+    games.sort_by_key(|game| (game.air_date, game.game_id));
+    games.dedup_by_key(|game| game.game_id);
+
+    games
+}
+
+/// Parses a link to a game (`showgame.php`), reading its show number and air date from the
+/// link's description when it has one
+pub(super) fn parse_game_link(link: ElementRef) -> Option<SeasonGame> {
+    let game_id = link
+        .value()
+        .attr("href")?
+        .split("game_id=")
+        .nth(1)?
+        .parse()
+        .ok()?;
+
+    let description = link.text().collect::<String>();
+    let description = GAME_DESCRIPTION_PATTERN.captures(&description);
+
+    Some(SeasonGame {
+        game_id,
+        show_number: description
+            .as_ref()
+            .and_then(|description| description[1].parse().ok()),
+        air_date: description
+            .as_ref()
+            .and_then(|description| parse_iso_date(&description[2])),
+    })
+}
+
+pub(super) fn parse_iso_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{parse_season_games, parse_seasons};
+
+    #[test]
+    fn parses_season_index() {
+        let seasons = parse_seasons(include_str!("../../fixtures/synthetic_seasons.html"));
+
+        let ids = seasons
+            .iter()
+            .map(|season| season.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec!["trebekpilots", "superjeopardy", "6", "15", "37", "38", "39"]
+        );
+
+        let latest = seasons.last().unwrap();
+        assert_eq!(latest.name, "Season 39");
+        assert_eq!(latest.first_aired, NaiveDate::from_ymd_opt(2022, 9, 12));
+        assert_eq!(latest.last_aired, NaiveDate::from_ymd_opt(2023, 7, 28));
+
+        assert_eq!(seasons[0].first_aired, None);
+    }
+
+    #[test]
+    fn parses_season_games_in_air_date_order() {
+        let games = parse_season_games(include_str!("../../fixtures/synthetic_season.html"));
+
+        let ids = games.iter().map(|game| game.game_id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![7467, 7504, 7515, 7516, 7517]);
+
+        let game = &games[2];
+        assert_eq!(game.show_number, Some(8745));
+        assert_eq!(game.air_date, NaiveDate::from_ymd_opt(2022, 11, 17));
+    }
+}
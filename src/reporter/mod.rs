@@ -1,73 +1,63 @@
-use std::{
-    fs,
-    io::{self, stdout},
-};
+//! Writes crawl results to a file or stdout
+//!
+//! This is public so that the command line binary, which is a separate crate, can write its
+//! output through it.
 
-use crossterm::{
-    cursor::{RestorePosition, SavePosition},
-    style::{Color, Print, ResetColor, SetForegroundColor},
-    terminal::{Clear, ClearType},
-    ExecutableCommand,
-};
-use serde::{Deserialize, Serialize};
+use std::io;
 
-use crate::{models::episode::JeopardyEpisode, serializer::SerializerBuilder};
+use tokio::io::{AsyncWrite, AsyncWriteExt, BufWriter};
+
+use crate::{
+    models::{episode::JeopardyEpisode, error::BuildError},
+    serializer::{OutputFormat, Serializer, SerializerBuilder},
+};
 
-#[derive(Serialize, Deserialize)]
 pub struct Reporter {
     outfile: Option<String>,
+    serializer: Serializer,
+    writer: Option<BufWriter<Box<dyn AsyncWrite + Unpin + Send>>>,
 }
 
 impl Reporter {
-    /// Writes json report to disk
-    pub async fn write(self, episodes: &Vec<JeopardyEpisode>) -> Result<(), io::Error> {
-        let json = SerializerBuilder::new()
-            .set_episodes(episodes.to_vec())
-            .build()
-            .expect("Could not build serializer from data")
-            .to_json();
-
-        if let Some(outfile) = self.outfile {
-            fs::write(outfile, json)
-        } else {
-            print!("{}", json);
-
-            Ok(())
-        }
+    /// Writes an episode to the outfile (or stdout), flushing it so it is on disk straight away
+    pub async fn write_episode(&mut self, episode: &JeopardyEpisode) -> Result<(), io::Error> {
+        let json = self.serializer.serialize_episode(episode);
+
+        self.write_output(&json).await
     }
 
-    /// Reports the progress of the current iteration
-    pub fn report_progress(
-        &self,
-        episode_no: u32,
-        curr: usize,
-        total: usize,
-    ) -> Result<(), io::Error> {
-        let symbols = vec!["\\", "|", "/", "―"];
-        let modulo = curr.rem_euclid(symbols.len());
-
-        let output = format!(" {} ", symbols[modulo]);
-        let formatted_episode_no = format!(" #{} ({} / {})", episode_no, (curr + 1), total);
-
-        stdout()
-            .execute(SavePosition)?
-            .execute(Clear(ClearType::CurrentLine))?
-            .execute(SetForegroundColor(Color::Green))?
-            .execute(Print(output))?
-            .execute(ResetColor)?
-            .execute(Print("Crawling episode"))?
-            .execute(SetForegroundColor(Color::Green))?
-            .execute(Print(formatted_episode_no))?
-            .execute(ResetColor)?
-            .execute(RestorePosition)?;
-
-        Ok(())
+    /// Finishes the report once every episode has been written
+    pub async fn finish(mut self) -> Result<(), io::Error> {
+        let json = self.serializer.finish();
+
+        self.write_output(json).await
+    }
+
+    /// Writes to the outfile (or stdout), opening it on first use
+    async fn write_output(&mut self, output: &str) -> Result<(), io::Error> {
+        if self.writer.is_none() {
+            let writer: Box<dyn AsyncWrite + Unpin + Send> = match &self.outfile {
+                Some(outfile) => Box::new(tokio::fs::File::create(outfile).await?),
+                None => Box::new(tokio::io::stdout()),
+            };
+
+            self.writer = Some(BufWriter::new(writer));
+        }
+
+        let writer = self
+            .writer
+            .as_mut()
+            .expect("Reporter writer was not opened");
+
+        writer.write_all(output.as_bytes()).await?;
+        writer.flush().await
     }
 }
 
 #[derive(Default)]
 pub struct ReporterBuilder {
     outfile: Option<String>,
+    format: Option<OutputFormat>,
 }
 
 impl ReporterBuilder {
@@ -81,9 +71,19 @@ impl ReporterBuilder {
         self
     }
 
-    pub fn build(&mut self) -> Result<Reporter, crate::models::error::Error> {
+    pub fn set_format(&mut self, format: OutputFormat) -> &mut Self {
+        self.format = Some(format);
+
+        self
+    }
+
+    pub fn build(&mut self) -> Result<Reporter, BuildError> {
         Ok(Reporter {
             outfile: self.outfile.to_owned(),
+            serializer: SerializerBuilder::new()
+                .set_format(self.format.unwrap_or_default())
+                .build()?,
+            writer: None,
         })
     }
 }
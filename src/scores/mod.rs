@@ -0,0 +1,255 @@
+use crate::models::{
+    episode::JeopardyEpisode,
+    question::{JeopardyQuestion, Round},
+    round::JeopardyRound,
+    score::{ScoreMismatch, ScoreProgression, ScoreSeries, ScoredClue},
+};
+
+/// Rebuilds every contestant's score after each clue of an episode
+///
+/// Rounds are replayed in the order they were played, and clues in the order they were picked. A
+/// right response earns the clue's value (or the daily double wager) and a wrong one loses it, and
+/// final jeopardy applies each contestant's wager. At the end of each round the running totals are
+/// checked against the scores j-archive printed, and any disagreement is recorded as a mismatch,
+/// which usually means the parser misread a clue.
+pub fn reconstruct(episode: &JeopardyEpisode) -> ScoreProgression {
+    let mut progression = Progression::new(episode);
+
+    for round in &episode.rounds {
+        match round.round {
+            Round::Jeopardy | Round::DoubleJeopardy => {
+                let mut questions: Vec<&JeopardyQuestion> = round.questions.iter().collect();
+                questions.sort_by_key(|question| question.order.unwrap_or(u32::MAX));
+
+                for question in questions {
+                    let value = if question.daily_double {
+                        question.wager
+                    } else {
+                        question.value
+                    };
+                    let value = value.map_or(0, |value| value as i32);
+
+                    let changes = question
+                        .responses
+                        .iter()
+                        .map(|response| {
+                            let change = if response.correct { value } else { -value };
+
+                            (response.contestant.as_str(), change)
+                        })
+                        .collect::<Vec<_>>();
+
+                    progression.play(question, &changes);
+                }
+            }
+            Round::FinalJeopardy => {
+                for question in &round.questions {
+                    let changes = question
+                        .final_wagers
+                        .iter()
+                        .map(|final_wager| {
+                            let wager = final_wager.wager.map_or(0, |wager| wager as i32);
+                            let change = if final_wager.correct { wager } else { -wager };
+
+                            (final_wager.contestant.as_str(), change)
+                        })
+                        .collect::<Vec<_>>();
+
+                    progression.play(question, &changes);
+                }
+            }
+            // a tiebreaker decides the winner without changing anyone's score
+            Round::Tiebreaker => continue,
+        }
+
+        progression.check(round);
+    }
+
+    progression.finish()
+}
+
+/// The running state of a score reconstruction
+struct Progression {
+    clues: Vec<ScoredClue>,
+    series: Vec<ScoreSeries>,
+    mismatches: Vec<ScoreMismatch>,
+}
+
+impl Progression {
+    /// Starts everyone on zero, in the order j-archive lists their podiums
+    fn new(episode: &JeopardyEpisode) -> Self {
+        let contestants = episode
+            .final_scores
+            .iter()
+            .map(|score| score.contestant.to_owned())
+            .collect::<Vec<_>>();
+
+        Progression {
+            clues: Vec::new(),
+            series: contestants
+                .into_iter()
+                .map(|contestant| ScoreSeries {
+                    contestant,
+                    scores: Vec::new(),
+                })
+                .collect(),
+            mismatches: Vec::new(),
+        }
+    }
+
+    /// Applies the score changes from a clue and records everyone's new score
+    fn play(&mut self, question: &JeopardyQuestion, changes: &[(&str, i32)]) {
+        for (contestant, _) in changes {
+            self.series_for(contestant);
+        }
+
+        for series in &mut self.series {
+            let current = series.scores.last().copied().unwrap_or(0);
+            let change: i32 = changes
+                .iter()
+                .filter(|(contestant, _)| *contestant == series.contestant)
+                .map(|(_, change)| change)
+                .sum();
+
+            series.scores.push(current + change);
+        }
+
+        self.clues.push(ScoredClue {
+            round: question.round,
+            column: question.column,
+            row: question.row,
+        });
+    }
+
+    /// Compares the running totals against the scores printed at the end of the round
+    fn check(&mut self, round: &JeopardyRound) {
+        for score in &round.scores {
+            let actual = self
+                .series
+                .iter()
+                .find(|series| series.contestant == score.contestant)
+                .and_then(|series| series.scores.last().copied())
+                .unwrap_or(0);
+
+            if actual != score.score {
+                self.mismatches.push(ScoreMismatch {
+                    round: round.round,
+                    contestant: score.contestant.to_owned(),
+                    expected: score.score,
+                    actual,
+                });
+            }
+        }
+    }
+
+    /// Finds a contestant's series, adding one if they haven't been seen before
+    fn series_for(&mut self, contestant: &str) -> &mut ScoreSeries {
+        let index = match self
+            .series
+            .iter()
+            .position(|series| series.contestant == contestant)
+        {
+            Some(index) => index,
+            None => {
+                self.series.push(ScoreSeries {
+                    contestant: contestant.to_string(),
+                    scores: vec![0; self.clues.len()],
+                });
+
+                self.series.len() - 1
+            }
+        };
+
+        &mut self.series[index]
+    }
+
+    fn finish(self) -> ScoreProgression {
+        ScoreProgression {
+            clues: self.clues,
+            series: self.series,
+            mismatches: self.mismatches,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use scraper::Html;
+
+    use super::reconstruct;
+    use crate::{models::question::Round, parser::JArchiveDocumentParser};
+
+    fn parse(html: &str, id: u32) -> crate::models::episode::JeopardyEpisode {
+        JArchiveDocumentParser::new(Html::parse_document(html), id)
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn rebuilds_scores_matching_the_score_tables() {
+        for (html, id) in [
+            (include_str!("../../fixtures/synthetic_game.html"), 7515),
+            (
+                include_str!("../../fixtures/synthetic_unrevealed.html"),
+                6942,
+            ),
+            (include_str!("../../fixtures/synthetic_classic.html"), 3320),
+            (
+                include_str!("../../fixtures/synthetic_tiebreaker.html"),
+                7012,
+            ),
+            (include_str!("../../fixtures/synthetic_partial.html"), 4305),
+        ] {
+            let progression = reconstruct(&parse(html, id));
+
+            assert!(
+                progression.mismatches.is_empty(),
+                "{0:?}",
+                progression.mismatches
+            );
+        }
+    }
+
+    #[test]
+    fn builds_a_series_per_contestant() {
+        let progression = reconstruct(&parse(
+            include_str!("../../fixtures/synthetic_game.html"),
+            7515,
+        ));
+
+        assert_eq!(progression.clues.len(), 61);
+        assert_eq!(progression.clues[60].round, Round::FinalJeopardy);
+
+        let finals = progression
+            .series
+            .iter()
+            .map(|series| {
+                assert_eq!(series.scores.len(), 61);
+
+                (series.contestant.as_str(), series.scores[60])
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            finals,
+            vec![("Mark", 9900), ("Priya", 18900), ("Kate", 10500)]
+        );
+    }
+
+    #[test]
+    fn flags_scores_which_disagree_with_the_score_tables() {
+        let mut episode = parse(include_str!("../../fixtures/synthetic_game.html"), 7515);
+
+        // pretend the parser misread the first clue's correct responder as wrong
+        episode.rounds[0].questions[0].responses[1].correct = false;
+
+        let mismatches = reconstruct(&episode).mismatches;
+
+        assert_eq!(mismatches.len(), 3);
+        assert!(mismatches
+            .iter()
+            .all(|mismatch| mismatch.contestant == "Mark"));
+        assert_eq!(mismatches[0].round, Round::Jeopardy);
+        assert_eq!(mismatches[0].expected - mismatches[0].actual, 400);
+    }
+}
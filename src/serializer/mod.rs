@@ -1,21 +1,80 @@
-use serde_json::to_string_pretty;
+use clap::ValueEnum;
+use serde_json::{from_str, to_string, to_string_pretty, Deserializer};
 
-use crate::models::{episode::JeopardyEpisode, error::Error};
+use crate::models::{episode::JeopardyEpisode, error::BuildError};
 
+/// The format episodes are written in
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// A single pretty printed JSON array
+    #[default]
+    Json,
+    /// One JSON object per line
+    JsonLines,
+}
+
+/// Incrementally serializes episodes, one at a time
 pub struct Serializer {
-    episodes: Vec<JeopardyEpisode>,
+    format: OutputFormat,
+    written: usize,
 }
 
 impl Serializer {
-    /// Converts episodes to jsons
-    pub fn to_json(&self) -> String {
-        to_string_pretty(&self.episodes).expect("Could not serialize episode data")
+    /// Converts an episode to json, including whatever has to come before it in the output
+    pub fn serialize_episode(&mut self, episode: &JeopardyEpisode) -> String {
+        let prefix = match (self.format, self.written) {
+            (OutputFormat::Json, 0) => "[\n",
+            (OutputFormat::Json, _) => ",\n",
+            (OutputFormat::JsonLines, _) => "",
+        };
+
+        self.written += 1;
+
+        match self.format {
+            OutputFormat::Json => {
+                let json = to_string_pretty(episode).expect("Could not serialize episode data");
+
+                // indent the object so the output matches a pretty printed array
+                let indented = json
+                    .lines()
+                    .map(|line| format!("  {0}", line))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                format!("{0}{1}", prefix, indented)
+            }
+            OutputFormat::JsonLines => {
+                let json = to_string(episode).expect("Could not serialize episode data");
+
+                format!("{0}{1}\n", prefix, json)
+            }
+        }
     }
+
+    /// Returns whatever has to come after the last episode in the output
+    pub fn finish(&self) -> &'static str {
+        match (self.format, self.written) {
+            (OutputFormat::Json, 0) => "[]",
+            (OutputFormat::Json, _) => "\n]",
+            (OutputFormat::JsonLines, _) => "",
+        }
+    }
+}
+
+/// Reads back episodes written in either output format
+pub fn deserialize_episodes(json: &str) -> Result<Vec<JeopardyEpisode>, serde_json::Error> {
+    if json.trim_start().starts_with('[') {
+        return from_str(json);
+    }
+
+    Deserializer::from_str(json)
+        .into_iter::<JeopardyEpisode>()
+        .collect()
 }
 
 #[derive(Default)]
 pub struct SerializerBuilder {
-    episodes: Option<Vec<JeopardyEpisode>>,
+    format: Option<OutputFormat>,
 }
 
 impl SerializerBuilder {
@@ -24,21 +83,82 @@ impl SerializerBuilder {
         SerializerBuilder::default()
     }
 
-    /// Sets the episodes
-    pub fn set_episodes(&mut self, episodes: Vec<JeopardyEpisode>) -> &mut Self {
-        self.episodes = Some(episodes);
+    /// Sets the output format
+    pub fn set_format(&mut self, format: OutputFormat) -> &mut Self {
+        self.format = Some(format);
 
         self
     }
 
     /// Builds the serializer object
-    pub fn build(&mut self) -> Result<Serializer, Error> {
-        let Some(episodes) = &self.episodes else {
-            return Err(Error::Static("Missing episodes"));
-        };
-
+    pub fn build(&mut self) -> Result<Serializer, BuildError> {
         Ok(Serializer {
-            episodes: episodes.to_vec(),
+            format: self.format.unwrap_or_default(),
+            written: 0,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::to_string_pretty;
+
+    use super::{deserialize_episodes, OutputFormat, SerializerBuilder};
+    use crate::models::episode::test_episode;
+
+    #[test]
+    fn writes_pretty_json_array_incrementally() {
+        let episodes = vec![test_episode(1), test_episode(2)];
+        let mut serializer = SerializerBuilder::new().build().unwrap();
+
+        let mut json = String::new();
+        for episode in &episodes {
+            json.push_str(&serializer.serialize_episode(episode));
+        }
+        json.push_str(serializer.finish());
+
+        assert_eq!(json, to_string_pretty(&episodes).unwrap());
+    }
+
+    #[test]
+    fn writes_empty_json_array() {
+        let serializer = SerializerBuilder::new().build().unwrap();
+
+        assert_eq!(serializer.finish(), "[]");
+    }
+
+    #[test]
+    fn writes_json_lines() {
+        let mut serializer = SerializerBuilder::new()
+            .set_format(OutputFormat::JsonLines)
+            .build()
+            .unwrap();
+
+        let first = serializer.serialize_episode(&test_episode(1));
+        let second = serializer.serialize_episode(&test_episode(2));
+
+        assert!(first.ends_with('\n') && !first.trim_end().contains('\n'));
+        assert!(second.starts_with('{'));
+        assert_eq!(serializer.finish(), "");
+    }
+
+    #[test]
+    fn reads_back_either_format() {
+        for format in [OutputFormat::Json, OutputFormat::JsonLines] {
+            let mut serializer = SerializerBuilder::new().set_format(format).build().unwrap();
+
+            let mut json = String::new();
+            for id in [1, 2] {
+                json.push_str(&serializer.serialize_episode(&test_episode(id)));
+            }
+            json.push_str(serializer.finish());
+
+            let ids = deserialize_episodes(&json)
+                .unwrap()
+                .iter()
+                .map(|episode| episode.id)
+                .collect::<Vec<_>>();
+            assert_eq!(ids, vec![1, 2]);
+        }
+    }
+}
@@ -1,44 +1,175 @@
 pub mod sanitizer {
-    use regex::Regex;
+    use clap::ValueEnum;
+    use scraper::{ElementRef, Html, Node};
 
-    pub enum Replacement<'a> {
-        String { to: &'a str, from: &'a str },
-        Regex { pattern: Regex, to: &'a str },
+    /// How much of a clue's formatting is kept
+    #[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+    pub enum TextFormat {
+        /// Plain text, with all formatting stripped
+        #[default]
+        Plain,
+        /// Markdown: `*italics*`, `**bold**` and hard line breaks, with underlines and
+        /// sub/superscripts left as inline html since markdown has no syntax for them
+        Markdown,
+        /// Html, keeping only emphasis, underlines, sub/superscripts and line breaks
+        Html,
     }
 
-    /// Removes invalid characters & sequences from a string
+    /// Elements which separate the text either side of them, rather than running into it
+    const SEPARATING_ELEMENTS: [&str; 14] = [
+        "br", "p", "div", "table", "tr", "td", "th", "ul", "ol", "li", "hr", "h1", "h2", "h3",
+    ];
+
+    /// Elements whose formatting survives in the markdown and html formats
+    const FORMATTING_ELEMENTS: [&str; 7] = ["i", "em", "b", "strong", "u", "sub", "sup"];
+
+    /// Characters which have to be escaped to appear literally in markdown
+    const MARKDOWN_SPECIAL_CHARACTERS: [char; 7] = ['\\', '*', '_', '`', '[', ']', '<'];
+
+    /// Stands in for a markdown line break until whitespace has been collapsed
+    const LINE_BREAK: char = '\n';
+
+    /// Converts a fragment of j-archive html into plain text
+    ///
+    /// Every tag is stripped (line breaks and block elements become spaces, inline ones like
+    /// `<i>` simply vanish), named and numeric entities are decoded by the html parser, and runs
+    /// of whitespace are collapsed into a single space.
     pub fn sanitize(s: &str) -> String {
-        let mut sanitized_string = s.to_owned();
-
-        // remove encoded values
-        let replacements = vec![
-            Replacement::String {
-                to: "&",
-                from: "&amp;", // encoded ampersand
-            },
-            Replacement::Regex {
-                pattern: Regex::new(r"</.+>").unwrap(), // closing html tags
-                to: " ",
-            },
-            Replacement::Regex {
-                pattern: Regex::new(r"<.+>").unwrap(), // opening html tags
-                to: " ",
-            },
-        ];
-
-        for replacement in replacements {
-            sanitized_string = match replacement {
-                Replacement::String { to, from } => sanitized_string.replace(from, to),
-                Replacement::Regex { pattern, to } => {
-                    pattern.replace(&sanitized_string, to).to_string()
+        sanitize_as(s, TextFormat::Plain)
+    }
+
+    /// Converts a fragment of j-archive html into text, keeping the formatting the format allows
+    ///
+    /// Anything that isn't kept is stripped as in [`sanitize`], and the text itself is escaped
+    /// for the format.
+    pub fn sanitize_as(s: &str, format: TextFormat) -> String {
+        let fragment = Html::parse_fragment(s);
+
+        let mut text = String::new();
+        push_text(fragment.root_element(), format, &mut text);
+
+        let line_separator = match format {
+            TextFormat::Markdown => "\\\n",
+            TextFormat::Plain | TextFormat::Html => " ",
+        };
+
+        text.split(LINE_BREAK)
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join(line_separator)
+    }
+
+    /// Appends the text inside an element, putting spaces around separating elements
+    fn push_text(element: ElementRef, format: TextFormat, text: &mut String) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(contents) => {
+                    // line breaks in the source are just whitespace
+                    let contents = contents.replace(char::is_whitespace, " ");
+
+                    text.push_str(&escape(&contents, format));
                 }
+                Node::Element(child_element) => {
+                    let Some(child) = ElementRef::wrap(child) else {
+                        continue;
+                    };
+
+                    let name = child_element.name();
+
+                    match (format, name) {
+                        (TextFormat::Markdown, "br") => text.push(LINE_BREAK),
+                        (TextFormat::Html, "br") => text.push_str("<br>"),
+                        (TextFormat::Markdown | TextFormat::Html, _)
+                            if FORMATTING_ELEMENTS.contains(&name) =>
+                        {
+                            let mut inner = String::new();
+                            push_text(child, format, &mut inner);
+
+                            let (open, close) = markers(name, format);
+                            push_formatted(&inner, &open, &close, text);
+                        }
+                        _ if SEPARATING_ELEMENTS.contains(&name) => {
+                            text.push(' ');
+                            push_text(child, format, text);
+                            text.push(' ');
+                        }
+                        _ => push_text(child, format, text),
+                    }
+                }
+                _ => {}
             }
         }
+    }
 
-        // trim start, end of string
-        sanitized_string = sanitized_string.trim().to_string();
+    /// The text either side of a formatting element's contents
+    fn markers(name: &str, format: TextFormat) -> (String, String) {
+        match (format, name) {
+            (TextFormat::Markdown, "i" | "em") => ("*".to_string(), "*".to_string()),
+            (TextFormat::Markdown, "b" | "strong") => ("**".to_string(), "**".to_string()),
+            _ => (format!("<{0}>", name), format!("</{0}>", name)),
+        }
+    }
+
+    /// Wraps formatted text in its markers, keeping surrounding whitespace outside of them
+    fn push_formatted(inner: &str, open: &str, close: &str, text: &mut String) {
+        let trimmed = inner.trim();
 
-        sanitized_string
+        if trimmed.is_empty() {
+            text.push_str(inner);
+            return;
+        }
+
+        let leading = &inner[..inner.len() - inner.trim_start().len()];
+        let trailing = &inner[inner.trim_end().len()..];
+
+        text.push_str(leading);
+        text.push_str(open);
+        text.push_str(trimmed);
+        text.push_str(close);
+        text.push_str(trailing);
+    }
+
+    /// Escapes the characters which would otherwise be read as formatting
+    fn escape(s: &str, format: TextFormat) -> String {
+        match format {
+            TextFormat::Plain => s.to_string(),
+            TextFormat::Markdown => {
+                let mut escaped = String::with_capacity(s.len());
+
+                for c in s.chars() {
+                    if MARKDOWN_SPECIAL_CHARACTERS.contains(&c) {
+                        escaped.push('\\');
+                    }
+
+                    escaped.push(c);
+                }
+
+                escaped
+            }
+            TextFormat::Html => s
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;"),
+        }
+    }
+}
+
+pub mod currency {
+    /// Parses a dollar amount as displayed on j-archive, e.g. `$1,000`, `-$200` or `DD: $2,000`
+    pub fn parse_amount(s: &str) -> Option<i32> {
+        let s = s.trim();
+        let s = s.strip_prefix("DD:").unwrap_or(s).trim();
+
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+
+        let digits = s.strip_prefix('$')?.replace(',', "");
+        let amount: i32 = digits.parse().ok()?;
+
+        Some(if negative { -amount } else { amount })
     }
 }
 
@@ -60,14 +191,80 @@ pub mod tests {
             assert_eq!(result, expected);
         }
 
+        #[test]
+        fn decodes_named_and_numeric_entities() {
+            assert_eq!(
+                sanitize(
+                    "&quot;Rock Around the Clock&quot; &amp; &quot;Shake, Rattle and Roll&quot;"
+                ),
+                "\"Rock Around the Clock\" & \"Shake, Rattle and Roll\""
+            );
+            assert_eq!(
+                sanitize("It&#39;s the city where Pok&eacute;mon Go was developed"),
+                "It's the city where Pokémon Go was developed"
+            );
+            assert_eq!(
+                sanitize("Caf&eacute; au lait &amp; cr&egrave;me br&ucirc;l&eacute;e"),
+                "Café au lait & crème brûlée"
+            );
+            assert_eq!(
+                sanitize("Hawai&#x2018;i &#8212; the Aloha State"),
+                "Hawai‘i — the Aloha State"
+            );
+            assert_eq!(sanitize("aired&#160;2022-11-17"), "aired 2022-11-17");
+        }
+
+        #[test]
+        fn leaves_unencoded_characters_alone() {
+            assert_eq!(sanitize("AT&T"), "AT&T");
+            assert_eq!(sanitize("5 < 6 & 7 > 2"), "5 < 6 & 7 > 2");
+        }
+
         #[test]
         fn removes_html_elements() {
             let result = sanitize("100 YEARS AGO<span class=\"nobreak\">--</span>1912");
-            let expected = "100 YEARS AGO -- 1912";
+            let expected = "100 YEARS AGO--1912";
 
             assert_eq!(result, expected);
         }
 
+        #[test]
+        fn keeps_text_between_elements() {
+            assert_eq!(
+                sanitize("<i>The Godfather</i> &amp; <i>Goodfellas</i> both star this actor"),
+                "The Godfather & Goodfellas both star this actor"
+            );
+            assert_eq!(sanitize("a <u>C</u>AT in a <u>H</u>AT"), "a CAT in a HAT");
+            assert_eq!(
+                sanitize(
+                    "(<a href=\"https://www.j-archive.com/media/2022-11-17_J_30.mp4\" target=\"_blank\">Sarah of the Clue Crew</a> reports from the <b>Louvre</b>.) <a href=\"https://www.j-archive.com/media/2022-11-17_J_30.jpg\" target=\"_blank\">Seen here</a>, this painting"
+                ),
+                "(Sarah of the Clue Crew reports from the Louvre.) Seen here, this painting"
+            );
+        }
+
+        #[test]
+        fn separates_line_breaks_and_blocks() {
+            assert_eq!(sanitize("ROCK<br />&amp;<br />ROLL"), "ROCK & ROLL");
+            assert_eq!(
+                sanitize("<p>first paragraph</p><p>second paragraph</p>"),
+                "first paragraph second paragraph"
+            );
+        }
+
+        #[test]
+        fn collapses_whitespace() {
+            assert_eq!(
+                sanitize("the  Sun \n\t King\r\n of France"),
+                "the Sun King of France"
+            );
+        }
+
+        #[test]
+        fn drops_comments() {
+            assert_eq!(sanitize("Paris<!-- capital -->, France"), "Paris, France");
+        }
+
         #[test]
         fn trims_str() {
             let result = sanitize(" sotto vocce ");
@@ -76,4 +273,106 @@ pub mod tests {
             assert_eq!(result, expected);
         }
     }
+
+    pub mod text_format_tests {
+        use super::super::sanitizer::{sanitize_as, TextFormat};
+
+        #[test]
+        fn keeps_emphasis_as_markdown() {
+            assert_eq!(
+                sanitize_as(
+                    "In <i>Moby-Dick</i>, Ahab is <b>captain</b> of this ship",
+                    TextFormat::Markdown
+                ),
+                "In *Moby-Dick*, Ahab is **captain** of this ship"
+            );
+            assert_eq!(
+                sanitize_as(
+                    "<i>The Raven </i>was published in 1845",
+                    TextFormat::Markdown
+                ),
+                "*The Raven* was published in 1845"
+            );
+        }
+
+        #[test]
+        fn keeps_emphasis_inside_words_in_markdown() {
+            // underscores only mark emphasis at word boundaries, asterisks work anywhere
+            assert_eq!(sanitize_as("<i>A</i>BC", TextFormat::Markdown), "*A*BC");
+            assert_eq!(
+                sanitize_as("un<i>believ</i>able", TextFormat::Markdown),
+                "un*believ*able"
+            );
+        }
+
+        #[test]
+        fn keeps_underlined_letters_in_markdown() {
+            assert_eq!(
+                sanitize_as(
+                    "The underlined letters spell it: <u>P</u>ARIS <u>E</u>NGLAND",
+                    TextFormat::Markdown
+                ),
+                "The underlined letters spell it: <u>P</u>ARIS <u>E</u>NGLAND"
+            );
+        }
+
+        #[test]
+        fn keeps_line_breaks_in_markdown() {
+            assert_eq!(
+                sanitize_as("ROCK<br />&amp;<br />ROLL", TextFormat::Markdown),
+                "ROCK\\\n&\\\nROLL"
+            );
+        }
+
+        #[test]
+        fn escapes_markdown_characters() {
+            assert_eq!(
+                sanitize_as("5 * 5 = 25 [approx.]", TextFormat::Markdown),
+                "5 \\* 5 = 25 \\[approx.\\]"
+            );
+        }
+
+        #[test]
+        fn keeps_whitelisted_html() {
+            assert_eq!(
+                sanitize_as(
+                    "<a href=\"https://www.j-archive.com/media/2022-11-17_J_30.jpg\" target=\"_blank\">Seen here</a>, <i>Water Lilies</i> by <span class=\"nobreak\">Monet</span><br />H<sub>2</sub>O",
+                    TextFormat::Html
+                ),
+                "Seen here, <i>Water Lilies</i> by Monet<br>H<sub>2</sub>O"
+            );
+        }
+
+        #[test]
+        fn escapes_html_text() {
+            assert_eq!(
+                sanitize_as("<b>AT&amp;T</b> &lt;3", TextFormat::Html),
+                "<b>AT&amp;T</b> &lt;3"
+            );
+        }
+    }
+
+    pub mod currency_tests {
+        use super::super::currency::parse_amount;
+
+        #[test]
+        fn parses_dollar_amounts() {
+            assert_eq!(parse_amount("$200"), Some(200));
+            assert_eq!(parse_amount(" $1,000 "), Some(1000));
+            assert_eq!(parse_amount("-$1,600"), Some(-1600));
+        }
+
+        #[test]
+        fn parses_daily_double_wagers() {
+            assert_eq!(parse_amount("DD: $2,000"), Some(2000));
+            assert_eq!(parse_amount("DD: $5"), Some(5));
+        }
+
+        #[test]
+        fn rejects_other_text() {
+            assert_eq!(parse_amount("Mark"), None);
+            assert_eq!(parse_amount("$"), None);
+            assert_eq!(parse_amount(""), None);
+        }
+    }
 }